@@ -0,0 +1,52 @@
+/// Embeds the puzzle inputs and examples below `data/`, and their parameters, into the library if the `embed_inputs` feature is enabled.
+/// The generated table maps paths like `data/inputs/01.txt` to the contents of the file, see `template::read_data_file`.
+use std::path::Path;
+use std::{env, fs};
+
+const FOLDERS: [&str; 2] = ["inputs", "examples"];
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_none() {
+        return;
+    }
+
+    let root = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("data");
+    let mut files = vec![];
+
+    for folder in FOLDERS {
+        let dir = root.join(folder);
+        println!("cargo:rerun-if-changed={}", dir.display());
+
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+            if path
+                .extension()
+                .is_some_and(|ext| ext == "txt" || ext == "params")
+            {
+                let name = path.file_name().unwrap().to_string_lossy().to_string();
+                files.push((format!("data/{folder}/{name}"), path));
+            }
+        }
+    }
+
+    // sorted, so that the generated file only changes if the data files do.
+    files.sort();
+
+    let entries = files
+        .iter()
+        .map(|(key, path)| {
+            format!(
+                "    ({key:?}, include_str!({:?})),\n",
+                path.display().to_string()
+            )
+        })
+        .collect::<String>();
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("embedded_data.rs");
+    fs::write(out_path, format!("&[\n{entries}]\n")).unwrap();
+}
@@ -1,11 +1,10 @@
-advent_of_code::solution!(1);
+use advent_of_code::matcher::MultiMatcher;
 
-const NUMS: [&str; 9] = [
-    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
-];
+advent_of_code::solution!(1);
 
-const NUMS_REVERSED: [&str; 9] = [
-    "eno", "owt", "eerht", "ruof", "evif", "xis", "neves", "thgie", "enin",
+const DIGITS: [&str; 18] = [
+    "1", "2", "3", "4", "5", "6", "7", "8", "9", "one", "two", "three", "four", "five", "six",
+    "seven", "eight", "nine",
 ];
 
 fn first_digit(input: &str) -> Option<char> {
@@ -16,61 +15,8 @@ fn last_digit(input: &str) -> Option<char> {
     input.chars().rev().find(|c| c.is_ascii_digit())
 }
 
-fn first_digit_spelled_out(input: &str) -> Option<u32> {
-    let mut input_string = String::from(input);
-    let mut tail;
-
-    if let Some(p) = NUMS.iter().position(|n| input_string.starts_with(n)) {
-        return Some(p as u32 + 1);
-    }
-
-    while let Some((c, tail_bytes)) = input_string.as_bytes().split_first() {
-        let c = char::from(*c);
-        tail = tail_bytes
-            .iter()
-            .map(|c| char::from(*c))
-            .collect::<String>();
-
-        if c.is_ascii_digit() {
-            return Some(c.to_digit(10).unwrap());
-        } else if let Some(p) = NUMS.into_iter().position(|n| tail.starts_with(n)) {
-            return Some(p as u32 + 1);
-        } else {
-            input_string = tail.clone();
-        }
-    }
-
-    None
-}
-
-fn last_digit_spelled_out(input: &str) -> Option<u32> {
-    let mut input_string = input.chars().rev().collect::<String>();
-    let mut tail;
-
-    if let Some(p) = NUMS_REVERSED
-        .iter()
-        .position(|n| input_string.starts_with(n))
-    {
-        return Some(p as u32 + 1);
-    }
-
-    while let Some((c, tail_bytes)) = input_string.as_bytes().split_first() {
-        let c = char::from(*c);
-        tail = tail_bytes
-            .iter()
-            .map(|c| char::from(*c))
-            .collect::<String>();
-
-        if c.is_ascii_digit() {
-            return Some(c.to_digit(10).unwrap());
-        } else if let Some(p) = NUMS_REVERSED.into_iter().position(|n| tail.starts_with(n)) {
-            return Some(p as u32 + 1);
-        } else {
-            input_string = tail.clone();
-        }
-    }
-
-    None
+fn digit_value(pattern: usize) -> u32 {
+    pattern as u32 % 9 + 1
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
@@ -82,22 +28,25 @@ pub fn part_one(input: &str) -> Option<u32> {
                 first_digit(line).unwrap(),
                 last_digit(line).unwrap()
             );
-            calibration.parse::<u32>().unwrap_or(0)
+            let calibration = calibration.parse::<u32>().unwrap_or(0);
+            advent_of_code::explain!(line, "calibration" => calibration);
+            calibration
         })
         .sum::<u32>()
         .into()
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
+    let matcher = MultiMatcher::new(DIGITS);
+
     input
         .lines()
         .map(|line| {
-            let calibration = format!(
-                "{}{}",
-                first_digit_spelled_out(line).unwrap(),
-                last_digit_spelled_out(line).unwrap()
-            );
-            calibration.parse::<u32>().unwrap_or(0)
+            let first = matcher.leftmost(line).unwrap();
+            let last = matcher.rightmost(line).unwrap();
+            let calibration = digit_value(first.pattern) * 10 + digit_value(last.pattern);
+            advent_of_code::explain!(line, "calibration" => calibration);
+            calibration
         })
         .sum::<u32>()
         .into()
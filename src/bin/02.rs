@@ -1,14 +1,10 @@
+use advent_of_code::parse::{self, ParseError};
+use advent_of_code::template::context::Context;
 use std::cmp::max;
 use std::str::FromStr;
 
 advent_of_code::solution!(2);
 
-const MAX_PICKS: Pick = Pick {
-    blue: 14,
-    green: 13,
-    red: 12,
-};
-
 #[derive(Debug)]
 struct Pick {
     blue: u32,
@@ -17,8 +13,17 @@ struct Pick {
 }
 
 impl Pick {
-    fn possible(&self) -> bool {
-        self.blue <= MAX_PICKS.blue && self.green <= MAX_PICKS.green && self.red <= MAX_PICKS.red
+    /// The cubes in the bag, which default to those of the real input.
+    fn max_picks(context: &Context) -> Pick {
+        Pick {
+            blue: context.param("blue", 14),
+            green: context.param("green", 13),
+            red: context.param("red", 12),
+        }
+    }
+
+    fn possible(&self, max_picks: &Pick) -> bool {
+        self.blue <= max_picks.blue && self.green <= max_picks.green && self.red <= max_picks.red
     }
 
     fn min_add(&self, other: &Pick) -> Pick {
@@ -34,25 +39,24 @@ impl Pick {
     }
 }
 
-impl FromStr for Pick {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts = s.split(',');
-
+impl Pick {
+    /// Parses a single pick, `s` being a sub-slice of the game `line`.
+    fn parse(line: &str, s: &str) -> Result<Self, ParseError> {
         let mut pick = Pick {
             blue: 0,
             green: 0,
             red: 0,
         };
 
-        for part in parts {
-            let (count, color) = part.trim().split_once(' ').unwrap();
+        for part in s.split(',') {
+            let (count, color) = parse::split_once(DAY, line, part.trim(), ' ')?;
+            let count = parse::number(DAY, line, count)?;
+
             match color {
-                "blue" => pick.blue = count.parse().unwrap(),
-                "green" => pick.green = count.parse().unwrap(),
-                "red" => pick.red = count.parse().unwrap(),
-                _ => panic!("Unknown color: {}", color),
+                "blue" => pick.blue = count,
+                "green" => pick.green = count,
+                "red" => pick.red = count,
+                _ => return Err(ParseError::locate(DAY, line, color, "unknown color")),
             }
         }
 
@@ -60,46 +64,59 @@ impl FromStr for Pick {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let total = input
-        .lines()
-        .filter_map(|line| {
-            let (game, picks) = line.split_once(':').unwrap();
-            let game_num = game.split_once(' ').unwrap().1.parse::<u32>().unwrap();
-            if picks.split(';').all(|pick| {
-                let pick = pick.parse::<Pick>().unwrap();
-                pick.possible()
-            }) {
-                Some(game_num)
-            } else {
-                None
-            }
+#[derive(Debug)]
+struct Game {
+    id: u32,
+    picks: Vec<Pick>,
+}
+
+impl FromStr for Game {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (game, picks) = parse::split_once(DAY, s, s, ':')?;
+        let (_, id) = parse::split_once(DAY, s, game, ' ')?;
+
+        Ok(Game {
+            id: parse::number(DAY, s, id)?,
+            picks: picks
+                .split(';')
+                .map(|pick| Pick::parse(s, pick))
+                .collect::<Result<_, _>>()?,
         })
-        .sum();
+    }
+}
+
+pub fn part_one(input: &str, context: &Context) -> Result<u32, ParseError> {
+    let max_picks = Pick::max_picks(context);
 
-    Some(total)
+    parse::lines::<Game>(input)
+        .filter_map(|game| match game {
+            Ok(game) => {
+                let possible = game.picks.iter().all(|pick| pick.possible(&max_picks));
+                advent_of_code::explain!(format!("Game {}", game.id), "possible" => possible);
+                possible.then_some(Ok(game.id))
+            }
+            Err(e) => Some(Err(e)),
+        })
+        .sum()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let total = input
-        .lines()
-        .map(|line| {
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    parse::lines::<Game>(input)
+        .map(|game| {
             let empty = Pick {
                 blue: 0,
                 green: 0,
                 red: 0,
             };
 
-            let game_picks = line.split_once(':').unwrap().1;
-            let picks = game_picks
-                .split(';')
-                .map(|pick| pick.parse::<Pick>().unwrap());
-            let min_pick = picks.fold(empty, |acc, pick| acc.min_add(&pick));
-            min_pick.power()
+            let game = game?;
+            let min_pick = game.picks.iter().fold(empty, |acc, pick| acc.min_add(pick));
+            advent_of_code::explain!(format!("Game {}", game.id), "power" => min_pick.power());
+            Ok(min_pick.power())
         })
-        .sum();
-
-    Some(total)
+        .sum()
 }
 
 #[cfg(test)]
@@ -108,13 +125,16 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(8));
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", DAY),
+            &Context::example(DAY, 1),
+        );
+        assert_eq!(result, Ok(8));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2286));
+        assert_eq!(result, Ok(2286));
     }
 }
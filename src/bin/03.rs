@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 advent_of_code::solution!(3);
 
-fn has_symbol_neighbor(chars: &Vec<Vec<char>>, y: usize, x: usize) -> bool {
+fn has_symbol_neighbor(chars: &[Vec<char>], y: usize, x: usize) -> bool {
     let ymax = chars.len() as isize;
     let xmax = chars[0].len() as isize;
 
@@ -27,7 +27,7 @@ fn has_symbol_neighbor(chars: &Vec<Vec<char>>, y: usize, x: usize) -> bool {
     false
 }
 
-fn star_neighbor_pos(chars: &Vec<Vec<char>>, y: usize, x: usize) -> Option<(isize, isize)> {
+fn star_neighbor_pos(chars: &[Vec<char>], y: usize, x: usize) -> Option<(isize, isize)> {
     let ymax = chars.len() as isize;
     let xmax = chars[0].len() as isize;
 
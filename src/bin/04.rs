@@ -1,8 +1,9 @@
+use advent_of_code::parse::{self, ParseError};
 use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
 use std::str::FromStr;
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(4, generator = generate);
 
 #[derive(Debug)]
 struct Card {
@@ -29,24 +30,21 @@ impl Card {
 }
 
 impl FromStr for Card {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, card_str) = s.split_once(':').ok_or("missing card")?;
+        let (_, card_str) = parse::split_once(DAY, s, s, ':')?;
 
-        let (winning_numbers, scratch_numbers) =
-            card_str.split_once('|').ok_or("missing numbers")?;
+        let (winning_numbers, scratch_numbers) = parse::split_once(DAY, s, card_str, '|')?;
 
         let winning = winning_numbers
             .split_whitespace()
-            .map(|n| n.parse::<u32>())
-            .collect::<Result<Vec<u32>, _>>()
-            .map_err(|e| format!("invalid winning numbers: {}", e))?;
+            .map(|n| parse::number(DAY, s, n))
+            .collect::<Result<Vec<u32>, _>>()?;
         let scratch = scratch_numbers
             .split_whitespace()
-            .map(|n| n.parse::<u32>())
-            .collect::<Result<Vec<u32>, _>>()
-            .map_err(|e| format!("invalid scratch numbers: {}", e))?;
+            .map(|n| parse::number(DAY, s, n))
+            .collect::<Result<Vec<u32>, _>>()?;
         Ok(Self {
             winning_numbers: winning,
             scratch_numbers: scratch,
@@ -54,19 +52,23 @@ impl FromStr for Card {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    input
-        .lines()
-        .map(|l| l.parse::<Card>().unwrap().points())
-        .sum::<u32>()
-        .into()
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    parse::lines::<Card>(input)
+        .enumerate()
+        .map(|(idx, card)| {
+            let card = card?;
+            advent_of_code::explain!(
+                format!("Card {}", idx + 1),
+                "matches" => card.matching_numbers(),
+                "points" => card.points(),
+            );
+            Ok(card.points())
+        })
+        .sum()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let cards = input
-        .lines()
-        .map(|l| (l.parse::<Card>().unwrap()))
-        .collect::<Vec<Card>>();
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    let cards = parse::lines::<Card>(input).collect::<Result<Vec<Card>, _>>()?;
 
     let mut card_nums = BTreeMap::<u32, u32>::new();
 
@@ -91,7 +93,28 @@ pub fn part_two(input: &str) -> Option<u32> {
         }
     });
 
-    card_nums.values().sum::<u32>().into()
+    for (idx, copies) in &card_nums {
+        advent_of_code::explain!(format!("Card {}", idx + 1), "copies" => copies);
+    }
+
+    Ok(card_nums.values().sum::<u32>())
+}
+
+/// Generates cards with `size` winning and scratch numbers each, exactly one of which matches.
+pub fn generate(size: usize) -> String {
+    const CARDS: usize = 10;
+
+    (1..=CARDS)
+        .map(|id| {
+            let winning = (0..size).map(|n| (2 * n).to_string());
+            let scratch = (0..size).map(|n| if n == 0 { 0 } else { 2 * n + 1 }.to_string());
+            format!(
+                "Card {id}: {} | {}\n",
+                winning.collect::<Vec<_>>().join(" "),
+                scratch.collect::<Vec<_>>().join(" ")
+            )
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -101,12 +124,19 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(13));
+        assert_eq!(result, Ok(13));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(30));
+        assert_eq!(result, Ok(30));
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(5);
+        assert_eq!(input.lines().count(), 10);
+        assert_eq!(part_one(&input), Ok(10));
     }
 }
@@ -1,9 +1,13 @@
+use advent_of_code::parse::{self, ParseError};
 use itertools::Itertools;
 use std::ops::Range;
 use std::ops::{Add, Div, Mul, Sub};
 use std::str::FromStr;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, [
+    fast => (part_one, part_two),
+    brute_force => (part_one, part_two_brute_force),
+]);
 
 trait MapRange: Sized {
     fn map_range(self, from: Range<Self>, to: Range<Self>) -> Self;
@@ -38,20 +42,25 @@ impl AlmanacMapEntry {
 }
 
 impl FromStr for AlmanacMapEntry {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let Some((dest, src, range)) = s.split_whitespace().collect_tuple() {
-            let from_start = src.parse::<u64>().map_err(|_| ())?;
-            let to_start = dest.parse::<u64>().map_err(|_| ())?;
-            let range = range.parse::<u64>().map_err(|_| ())?;
+            let from_start = parse::number::<u64>(DAY, s, src)?;
+            let to_start = parse::number::<u64>(DAY, s, dest)?;
+            let range = parse::number::<u64>(DAY, s, range)?;
 
             Ok(AlmanacMapEntry {
                 input_range: from_start..from_start + range,
                 output_range: to_start..to_start + range,
             })
         } else {
-            Err(())
+            Err(ParseError::locate(
+                DAY,
+                s,
+                s,
+                "expected `<destination> <source> <length>`, found",
+            ))
         }
     }
 }
@@ -194,7 +203,20 @@ impl Almanac {
         min_location
     }
 
-    fn from_str_ranged(s: &str) -> Result<Self, ()> {
+    /// Maps every seed of every range on its own. Only feasible for small inputs, but a useful oracle.
+    fn min_seed_location_brute_force(&self) -> u64 {
+        self.seeds
+            .iter()
+            .flat_map(|seed| match seed {
+                Seed::Spot(spot) => *spot..*spot + 1,
+                Seed::Range(range) => range.clone(),
+            })
+            .map(|seed| self.map_seed_to_location(seed))
+            .min()
+            .unwrap_or(u64::MAX)
+    }
+
+    fn parse(s: &str, ranged: bool) -> Result<Self, ParseError> {
         let mut seeds = Vec::new();
         let mut seed_map = AlmanacMap::new();
         let mut soil_map = AlmanacMap::new();
@@ -204,16 +226,30 @@ impl Almanac {
         let mut temperature_map = AlmanacMap::new();
         let mut humidity_map = AlmanacMap::new();
 
-        let mut iter = s.lines();
+        let mut iter = s.lines().enumerate();
 
-        while let Some(line) = iter.next() {
-            if line.starts_with("seeds") {
-                let (_, seed_str) = line.split(':').collect_tuple().unwrap();
+        while let Some((_, line)) = iter.next() {
+            let map = if line.starts_with("seeds") {
+                let (_, seed_str) = parse::split_once(DAY, s, line, ':')?;
 
                 let parsed_seeds = seed_str
                     .split_whitespace()
-                    .map(|s| s.parse::<u64>().unwrap())
-                    .collect::<Vec<u64>>();
+                    .map(|n| parse::number(DAY, s, n))
+                    .collect::<Result<Vec<u64>, _>>()?;
+
+                if !ranged {
+                    seeds = parsed_seeds.into_iter().map(Seed::Spot).collect();
+                    continue;
+                }
+
+                if parsed_seeds.len() % 2 != 0 {
+                    return Err(ParseError::locate(
+                        DAY,
+                        s,
+                        seed_str,
+                        "expected pairs of seed ranges in",
+                    ));
+                }
 
                 seeds = parsed_seeds
                     .iter()
@@ -225,62 +261,34 @@ impl Almanac {
                         Seed::Range(start..(start + range))
                     })
                     .collect();
+                continue;
             } else if line.starts_with("seed-to-soil") {
-                for line in iter.by_ref() {
-                    if line.is_empty() {
-                        break;
-                    }
-
-                    seed_map.push(line.parse::<AlmanacMapEntry>().unwrap());
-                }
+                &mut seed_map
             } else if line.starts_with("soil") {
-                for line in iter.by_ref() {
-                    if line.is_empty() {
-                        break;
-                    }
-
-                    soil_map.push(line.parse::<AlmanacMapEntry>().unwrap());
-                }
+                &mut soil_map
             } else if line.starts_with("fertilizer") {
-                for line in iter.by_ref() {
-                    if line.is_empty() {
-                        break;
-                    }
-
-                    fertilizer_map.push(line.parse::<AlmanacMapEntry>().unwrap());
-                }
+                &mut fertilizer_map
             } else if line.starts_with("water") {
-                for line in iter.by_ref() {
-                    if line.is_empty() {
-                        break;
-                    }
-
-                    water_map.push(line.parse::<AlmanacMapEntry>().unwrap());
-                }
+                &mut water_map
             } else if line.starts_with("light") {
-                for line in iter.by_ref() {
-                    if line.is_empty() {
-                        break;
-                    }
-
-                    sunlight_map.push(line.parse::<AlmanacMapEntry>().unwrap());
-                }
+                &mut sunlight_map
             } else if line.starts_with("temperature") {
-                for line in iter.by_ref() {
-                    if line.is_empty() {
-                        break;
-                    }
-
-                    temperature_map.push(line.parse::<AlmanacMapEntry>().unwrap());
-                }
+                &mut temperature_map
             } else if line.starts_with("humidity") {
-                for line in iter.by_ref() {
-                    if line.is_empty() {
-                        break;
-                    }
+                &mut humidity_map
+            } else {
+                continue;
+            };
 
-                    humidity_map.push(line.parse::<AlmanacMapEntry>().unwrap());
+            for (idx, line) in iter.by_ref() {
+                if line.is_empty() {
+                    break;
                 }
+
+                map.push(
+                    line.parse::<AlmanacMapEntry>()
+                        .map_err(|e| e.on_line(idx + 1))?,
+                );
             }
         }
 
@@ -298,108 +306,26 @@ impl Almanac {
 }
 
 impl FromStr for Almanac {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut seeds = Vec::new();
-        let mut seed_map = AlmanacMap::new();
-        let mut soil_map = AlmanacMap::new();
-        let mut fertilizer_map = AlmanacMap::new();
-        let mut water_map = AlmanacMap::new();
-        let mut sunlight_map = AlmanacMap::new();
-        let mut temperature_map = AlmanacMap::new();
-        let mut humidity_map = AlmanacMap::new();
-
-        let mut iter = s.lines();
-
-        while let Some(line) = iter.next() {
-            if line.starts_with("seeds") {
-                let (_, seed_str) = line.split(':').collect_tuple().unwrap();
-
-                seeds = seed_str
-                    .split_whitespace()
-                    .map(|s| Seed::Spot(s.parse::<u64>().unwrap()))
-                    .collect();
-            } else if line.starts_with("seed-to-soil") {
-                for line in iter.by_ref() {
-                    if line.is_empty() {
-                        break;
-                    }
-
-                    seed_map.push(line.parse::<AlmanacMapEntry>().unwrap());
-                }
-            } else if line.starts_with("soil") {
-                for line in iter.by_ref() {
-                    if line.is_empty() {
-                        break;
-                    }
-
-                    soil_map.push(line.parse::<AlmanacMapEntry>().unwrap());
-                }
-            } else if line.starts_with("fertilizer") {
-                for line in iter.by_ref() {
-                    if line.is_empty() {
-                        break;
-                    }
-
-                    fertilizer_map.push(line.parse::<AlmanacMapEntry>().unwrap());
-                }
-            } else if line.starts_with("water") {
-                for line in iter.by_ref() {
-                    if line.is_empty() {
-                        break;
-                    }
-
-                    water_map.push(line.parse::<AlmanacMapEntry>().unwrap());
-                }
-            } else if line.starts_with("light") {
-                for line in iter.by_ref() {
-                    if line.is_empty() {
-                        break;
-                    }
-
-                    sunlight_map.push(line.parse::<AlmanacMapEntry>().unwrap());
-                }
-            } else if line.starts_with("temperature") {
-                for line in iter.by_ref() {
-                    if line.is_empty() {
-                        break;
-                    }
-
-                    temperature_map.push(line.parse::<AlmanacMapEntry>().unwrap());
-                }
-            } else if line.starts_with("humidity") {
-                for line in iter.by_ref() {
-                    if line.is_empty() {
-                        break;
-                    }
-
-                    humidity_map.push(line.parse::<AlmanacMapEntry>().unwrap());
-                }
-            }
-        }
-
-        Ok(Almanac {
-            seeds,
-            seed_map,
-            soil_map,
-            fertilizer_map,
-            water_map,
-            sunlight_map,
-            temperature_map,
-            humidity_map,
-        })
+        Almanac::parse(s, false)
     }
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let almanac = input.parse::<Almanac>().unwrap();
-    Some(almanac.min_seed_location())
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    let almanac = input.parse::<Almanac>()?;
+    Ok(almanac.min_seed_location())
+}
+
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let almanac = Almanac::parse(input, true)?;
+    Ok(almanac.min_seed_location())
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let almanac = Almanac::from_str_ranged(input).unwrap();
-    Some(almanac.min_seed_location())
+pub fn part_two_brute_force(input: &str) -> Result<u64, ParseError> {
+    let almanac = Almanac::parse(input, true)?;
+    Ok(almanac.min_seed_location_brute_force())
 }
 
 #[cfg(test)]
@@ -409,12 +335,21 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(35));
+        assert_eq!(result, Ok(35));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(46));
+        assert_eq!(result, Ok(46));
+    }
+
+    #[test]
+    fn test_part_two_brute_force() {
+        let result = part_two_brute_force(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(
+            result,
+            part_two(&advent_of_code::template::read_file("examples", DAY))
+        );
     }
 }
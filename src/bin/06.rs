@@ -1,7 +1,12 @@
+use advent_of_code::math;
+use advent_of_code::parse::{self, ParseError};
 use std::iter::zip;
 use std::str::FromStr;
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(6, [
+    fast => (part_one, part_two),
+    naive => (part_one_naive, part_two_naive),
+]);
 
 struct Race {
     time: u64,
@@ -9,25 +14,32 @@ struct Race {
 }
 
 impl Race {
-    fn min_hold(&self) -> u64 {
-        (1..self.time)
-            .find(|t| t * (self.time - t) > self.distance)
-            .unwrap_or(self.time)
+    fn num_ways_to_win(&self) -> u64 {
+        math::race_wins(self.time, self.distance)
     }
 
-    fn max_hold(&self) -> u64 {
-        (1..self.time)
-            .rfind(|t| t * (self.time - t) > self.distance)
-            .unwrap_or(0)
+    /// Tries every hold time, which takes time linear in the race time.
+    fn num_ways_to_win_naive(&self) -> u64 {
+        (0..=self.time)
+            .filter(|t| t * (self.time - t) > self.distance)
+            .count() as u64
     }
+}
 
-    fn num_ways_to_win(&self) -> u64 {
-        self.max_hold() - self.min_hold() + 1
-    }
+/// Concatenates all digits of a `Time:` or `Distance:` line, ignoring the spaces between them.
+fn kerned_number(s: &str, line: &str) -> Result<u64, ParseError> {
+    let (_, numbers) = parse::split_once(DAY, s, line, ':')?;
+
+    numbers
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .parse::<u64>()
+        .map_err(|_| ParseError::locate(DAY, s, numbers.trim(), "invalid number"))
 }
 
 impl FromStr for Race {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut time = 0;
@@ -40,19 +52,9 @@ impl FromStr for Race {
             }
 
             if line.starts_with("Time") {
-                time = line
-                    .chars()
-                    .filter(|c| c.is_ascii_digit())
-                    .collect::<String>()
-                    .parse::<u64>()
-                    .unwrap();
+                time = kerned_number(s, line)?;
             } else if line.starts_with("Distance") {
-                distance = line
-                    .chars()
-                    .filter(|c| c.is_ascii_digit())
-                    .collect::<String>()
-                    .parse::<u64>()
-                    .unwrap();
+                distance = kerned_number(s, line)?;
             }
         }
 
@@ -65,13 +67,26 @@ struct Races {
 }
 
 impl Races {
-    fn product(&self) -> u64 {
-        self.races.iter().map(|r| r.num_ways_to_win()).product()
+    fn product(&self, num_ways_to_win: impl Fn(&Race) -> u64) -> u64 {
+        self.races
+            .iter()
+            .enumerate()
+            .map(|(idx, race)| {
+                let wins = num_ways_to_win(race);
+                advent_of_code::explain!(
+                    format!("Race {}", idx + 1),
+                    "time" => race.time,
+                    "distance" => race.distance,
+                    "wins" => wins,
+                );
+                wins
+            })
+            .product()
     }
 }
 
 impl FromStr for Races {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut times = Vec::new();
@@ -85,18 +100,18 @@ impl FromStr for Races {
             }
 
             if line.starts_with("Time") {
-                let times_str = line.split(':').nth(1).unwrap().trim();
+                let (_, times_str) = parse::split_once(DAY, s, line, ':')?;
 
                 times = times_str
                     .split_whitespace()
-                    .map(|s| s.parse::<u64>().unwrap())
-                    .collect();
+                    .map(|n| parse::number(DAY, s, n))
+                    .collect::<Result<_, _>>()?;
             } else if line.starts_with("Distance") {
-                let distances_str = line.split(':').nth(1).unwrap().trim();
+                let (_, distances_str) = parse::split_once(DAY, s, line, ':')?;
                 distances = distances_str
                     .split_whitespace()
-                    .map(|s| s.parse::<u64>().unwrap())
-                    .collect();
+                    .map(|n| parse::number(DAY, s, n))
+                    .collect::<Result<_, _>>()?;
             }
         }
 
@@ -111,14 +126,24 @@ impl FromStr for Races {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let races = input.parse::<Races>().unwrap();
-    Some(races.product())
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    let races = input.parse::<Races>()?;
+    Ok(races.product(Race::num_ways_to_win))
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let race = input.parse::<Race>().unwrap();
-    Some(race.num_ways_to_win())
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let race = input.parse::<Race>()?;
+    Ok(race.num_ways_to_win())
+}
+
+pub fn part_one_naive(input: &str) -> Result<u64, ParseError> {
+    let races = input.parse::<Races>()?;
+    Ok(races.product(Race::num_ways_to_win_naive))
+}
+
+pub fn part_two_naive(input: &str) -> Result<u64, ParseError> {
+    let race = input.parse::<Race>()?;
+    Ok(race.num_ways_to_win_naive())
 }
 
 #[cfg(test)]
@@ -128,12 +153,19 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(288));
+        assert_eq!(result, Ok(288));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(71503));
+        assert_eq!(result, Ok(71503));
+    }
+
+    #[test]
+    fn test_naive() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        assert_eq!(part_one_naive(&input), part_one(&input));
+        assert_eq!(part_two_naive(&input), part_two(&input));
     }
 }
@@ -0,0 +1,236 @@
+/// Ranking utilities for poker-like card puzzles.
+use std::cmp::Ordering;
+
+/// The type of a hand, from weakest to strongest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl HandType {
+    /// Classifies a hand from the number of copies of each distinct card it holds, in any order,
+    /// e.g. `[2, 3]` is a full house.
+    pub fn classify(counts: &[u8]) -> Self {
+        Self::classify_with_wildcards(counts, 0)
+    }
+
+    /// Classifies a hand like [`HandType::classify`], with `wildcards` additional cards that may stand in for any card.
+    /// `counts` must not include the wildcards themselves.
+    pub fn classify_with_wildcards(counts: &[u8], wildcards: u8) -> Self {
+        let (mut first, mut second) = (0, 0);
+
+        for &count in counts {
+            if count > first {
+                second = first;
+                first = count;
+            } else if count > second {
+                second = count;
+            }
+        }
+
+        // wildcards are always best spent on the most common card.
+        match (first + wildcards, second) {
+            (5.., _) => HandType::FiveOfAKind,
+            (4, _) => HandType::FourOfAKind,
+            (3, 2) => HandType::FullHouse,
+            (3, _) => HandType::ThreeOfAKind,
+            (2, 2) => HandType::TwoPair,
+            (2, _) => HandType::OnePair,
+            _ => HandType::HighCard,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Maps cards to their strength according to a custom alphabet.
+#[derive(Debug, Clone)]
+pub struct CardOrder {
+    strengths: [u8; 256],
+}
+
+impl CardOrder {
+    /// Creates a [`CardOrder`] from an alphabet of ASCII cards, listed from weakest to strongest.
+    pub const fn new(alphabet: &str) -> Self {
+        let bytes = alphabet.as_bytes();
+        assert!(bytes.len() < 256, "card alphabets are limited to 255 cards");
+
+        let mut strengths = [0; 256];
+        let mut i = 0;
+
+        while i < bytes.len() {
+            strengths[bytes[i] as usize] = i as u8 + 1;
+            i += 1;
+        }
+
+        Self { strengths }
+    }
+
+    /// Returns the strength of `card` (starting at `1` for the weakest card), or [`None`] if it is not part of the alphabet.
+    pub fn strength(&self, card: char) -> Option<u8> {
+        if !card.is_ascii() {
+            return None;
+        }
+
+        match self.strengths[card as usize] {
+            0 => None,
+            s => Some(s),
+        }
+    }
+
+    /// Compares two cards by strength. Cards outside the alphabet are weaker than all others.
+    pub fn cmp(&self, a: char, b: char) -> Ordering {
+        self.strength(a).cmp(&self.strength(b))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The strength of a hand under a [`Ranking`]. Ranks compare by hand type first and card by card second.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Rank {
+    pub hand_type: HandType,
+    cards: u64,
+}
+
+/// A hand ranking rule set: a card order and an optional wildcard.
+#[derive(Debug, Clone)]
+pub struct Ranking {
+    order: CardOrder,
+    wildcard: Option<char>,
+}
+
+impl Ranking {
+    /// The longest hand that can be ranked.
+    pub const MAX_HAND_SIZE: usize = 8;
+
+    /// Creates a [`Ranking`] without wildcards, with cards ordered from weakest to strongest.
+    pub const fn new(alphabet: &str) -> Self {
+        Self {
+            order: CardOrder::new(alphabet),
+            wildcard: None,
+        }
+    }
+
+    /// Makes `card` a wildcard that upgrades the hand type by standing in for the most common card.
+    /// Its strength when comparing hands card by card is still determined by the alphabet.
+    #[must_use]
+    pub const fn with_wildcard(mut self, card: char) -> Self {
+        self.wildcard = Some(card);
+        self
+    }
+
+    pub fn order(&self) -> &CardOrder {
+        &self.order
+    }
+
+    /// Ranks a hand. Returns [`None`] if the hand holds cards outside the alphabet or exceeds [`Ranking::MAX_HAND_SIZE`].
+    pub fn rank(&self, hand: &str) -> Option<Rank> {
+        let mut counts = [0u8; 256];
+        let mut wildcards = 0;
+        let mut cards = 0u64;
+        let mut size = 0;
+
+        for card in hand.chars() {
+            size += 1;
+            if size > Self::MAX_HAND_SIZE {
+                return None;
+            }
+
+            cards = cards << 8 | u64::from(self.order.strength(card)?);
+
+            if Some(card) == self.wildcard {
+                wildcards += 1;
+            } else {
+                counts[card as usize] += 1;
+            }
+        }
+
+        Some(Rank {
+            hand_type: HandType::classify_with_wildcards(&counts, wildcards),
+            cards,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{CardOrder, HandType, Ranking};
+
+    const PLAIN: Ranking = Ranking::new("23456789TJQKA");
+    const JOKERS: Ranking = Ranking::new("J23456789TQKA").with_wildcard('J');
+
+    #[test]
+    fn classifies_histograms() {
+        assert_eq!(HandType::classify(&[5]), HandType::FiveOfAKind);
+        assert_eq!(HandType::classify(&[1, 4]), HandType::FourOfAKind);
+        assert_eq!(HandType::classify(&[3, 2]), HandType::FullHouse);
+        assert_eq!(HandType::classify(&[2, 3]), HandType::FullHouse);
+        assert_eq!(HandType::classify(&[1, 3, 1]), HandType::ThreeOfAKind);
+        assert_eq!(HandType::classify(&[2, 1, 2]), HandType::TwoPair);
+        assert_eq!(HandType::classify(&[0, 2, 1, 1, 1]), HandType::OnePair);
+        assert_eq!(HandType::classify(&[1, 2, 1, 1]), HandType::OnePair);
+        assert_eq!(HandType::classify(&[1, 1, 1, 1, 1]), HandType::HighCard);
+        assert_eq!(HandType::classify(&[]), HandType::HighCard);
+    }
+
+    #[test]
+    fn wildcards_upgrade_hand_type() {
+        assert_eq!(
+            HandType::classify_with_wildcards(&[], 5),
+            HandType::FiveOfAKind
+        );
+        assert_eq!(
+            HandType::classify_with_wildcards(&[2, 2], 1),
+            HandType::FullHouse
+        );
+        assert_eq!(
+            HandType::classify_with_wildcards(&[1, 1, 1], 2),
+            HandType::ThreeOfAKind
+        );
+    }
+
+    #[test]
+    fn card_order() {
+        let order = CardOrder::new("23456789TJQKA");
+        assert_eq!(order.strength('2'), Some(1));
+        assert_eq!(order.strength('A'), Some(13));
+        assert_eq!(order.strength('X'), None);
+        assert_eq!(order.strength('é'), None);
+        assert!(order.cmp('K', 'T').is_gt());
+    }
+
+    #[test]
+    fn ranks_hands() {
+        assert!(PLAIN.rank("33332") > PLAIN.rank("2AAAA"));
+        assert!(PLAIN.rank("77888") > PLAIN.rank("77788"));
+        assert!(PLAIN.rank("KK677") > PLAIN.rank("KTJJT"));
+        assert_eq!(PLAIN.rank("QJJQ2").unwrap().hand_type, HandType::TwoPair);
+        assert_eq!(PLAIN.rank("QJJX2"), None);
+        assert_eq!(PLAIN.rank("222222222"), None);
+    }
+
+    #[test]
+    fn ranks_hands_with_wildcards() {
+        assert_eq!(
+            JOKERS.rank("QJJQ2").unwrap().hand_type,
+            HandType::FourOfAKind
+        );
+        assert_eq!(
+            JOKERS.rank("JJJJJ").unwrap().hand_type,
+            HandType::FiveOfAKind
+        );
+        assert!(JOKERS.rank("KTJJT") > JOKERS.rank("QQQJA"));
+        assert!(JOKERS.rank("JKKK2") < JOKERS.rank("QQQQ2"));
+    }
+}
+
+/* -------------------------------------------------------------------------- */
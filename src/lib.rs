@@ -1,4 +1,9 @@
+pub mod cards;
 mod day;
+pub mod matcher;
+pub mod math;
+pub mod ocr;
+pub mod parse;
 pub mod template;
 
 pub use day::*;
@@ -1,9 +1,17 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, analyze, compare, download, inspect, minimize, read, scaffold, solve, watch,
+};
+use advent_of_code::template::output;
 use args::{parse, AppArguments};
 
 mod args {
     use std::process;
 
+    use advent_of_code::template::analyze::parse_sizes;
+    use advent_of_code::template::commands::{all, analyze, compare, minimize, solve, watch};
+    use advent_of_code::template::limits::{parse_bytes, parse_seconds, Limits};
+    use advent_of_code::template::output::Verbosity;
+    use advent_of_code::template::profiles::parse_profiles;
     use advent_of_code::Day;
 
     pub enum AppArguments {
@@ -13,28 +21,71 @@ mod args {
         Read {
             day: Day,
         },
+        Inspect {
+            day: Day,
+        },
         Scaffold {
             day: Day,
         },
         Solve {
             day: Day,
-            release: bool,
-            time: bool,
-            submit: Option<u8>,
+            options: solve::Options,
         },
         All {
-            release: bool,
-            time: bool,
+            options: all::Options,
+        },
+        Minimize {
+            day: Day,
+            options: minimize::Options,
+        },
+        Analyze {
+            day: Day,
+            options: analyze::Options,
         },
+        Compare {
+            options: compare::Options,
+        },
+        Watch {
+            day: Day,
+            options: watch::Options,
+        },
+    }
+
+    fn parse_part(s: &str) -> Result<u8, String> {
+        match s.parse() {
+            Ok(part @ (1 | 2)) => Ok(part),
+            _ => Err("expecting a part number of 1 or 2".into()),
+        }
+    }
+
+    fn parse_limits(args: &mut pico_args::Arguments) -> Result<Limits, pico_args::Error> {
+        Ok(Limits {
+            max_memory: args.opt_value_from_fn("--max-memory", parse_bytes)?,
+            max_cpu_time: args.opt_value_from_fn("--max-cpu-time", parse_seconds)?,
+            stack_size: args.opt_value_from_fn("--stack-size", parse_bytes)?,
+        })
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    pub fn parse() -> Result<(AppArguments, Verbosity), Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+        let verbosity =
+            Verbosity::from_flags(args.contains("--quiet"), args.contains("--verbose"))?;
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
+                options: all::Options {
+                    release: args.contains("--release"),
+                    time: args.contains("--time"),
+                    track_allocs: args.contains("--track-allocs"),
+                    limits: parse_limits(&mut args)?,
+                    pin_cpu: args.opt_value_from_str("--pin-cpu")?,
+                    profiles: args
+                        .opt_value_from_fn("--profiles", parse_profiles)?
+                        .unwrap_or_default(),
+                    enforce_budgets: args.contains("--enforce-budgets"),
+                },
             },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
@@ -42,14 +93,67 @@ mod args {
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
+            Some("inspect") => AppArguments::Inspect {
+                day: args.free_from_str()?,
+            },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
             },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
+                options: solve::Options {
+                    release: args.contains("--release"),
+                    track_allocs: args.contains("--track-allocs"),
+                    limits: parse_limits(&mut args)?,
+                    pin_cpu: args.opt_value_from_str("--pin-cpu")?,
+                    part: args.opt_value_from_fn("--part", parse_part)?,
+                    submit_part: args.opt_value_from_fn("--submit", parse_part)?,
+                    variant: args.opt_value_from_str("--variant")?,
+                    all_variants: args.contains("--all-variants"),
+                    input: args.opt_value_from_str("--input")?,
+                    explain: args.contains("--explain"),
+                    time: args.contains("--time"),
+                },
+            },
+            Some("minimize") => AppArguments::Minimize {
+                day: args.free_from_str()?,
+                options: minimize::Options {
+                    release: args.contains("--release"),
+                    part: args.opt_value_from_fn("--part", parse_part)?,
+                    input: args.opt_value_from_str("--input")?,
+                    limits: parse_limits(&mut args)?,
+                },
+            },
+            Some("analyze") => AppArguments::Analyze {
+                day: args.free_from_str()?,
+                options: analyze::Options {
+                    release: args.contains("--release"),
+                    part: args.opt_value_from_fn("--part", parse_part)?,
+                    variant: args.opt_value_from_str("--variant")?,
+                    all_variants: args.contains("--all-variants"),
+                    sizes: args
+                        .opt_value_from_fn("--sizes", |s| parse_sizes(s).map(|_| s.to_string()))?,
+                    svg: args.opt_value_from_str("--svg")?,
+                },
+            },
+            Some("compare") => AppArguments::Compare {
+                options: compare::Options {
+                    rev_a: args.free_from_str()?,
+                    rev_b: args.free_from_str()?,
+                    days: {
+                        let mut days = vec![];
+                        while let Some(day) = args.opt_free_from_str()? {
+                            days.push(day);
+                        }
+                        days
+                    },
+                },
+            },
+            Some("watch") => AppArguments::Watch {
+                day: args.free_from_str()?,
+                options: watch::Options {
+                    release: args.contains("--release"),
+                },
             },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
@@ -66,7 +170,41 @@ mod args {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
 
-        Ok(app_args)
+        let is_timed = match &app_args {
+            AppArguments::All { options } => options.time,
+            AppArguments::Solve { options, .. } => options.time,
+            _ => false,
+        };
+
+        if let AppArguments::All { options } = &app_args {
+            if options.enforce_budgets && !options.time {
+                return Err("`--enforce-budgets` requires `--time`".into());
+            }
+        }
+
+        if let AppArguments::Analyze { options, .. } = &app_args {
+            if options.all_variants && options.variant.is_some() {
+                return Err("`--variant` and `--all-variants` cannot be combined".into());
+            }
+        }
+
+        if let AppArguments::Solve { options, .. } = &app_args {
+            if options.all_variants && options.variant.is_some() {
+                return Err("`--variant` and `--all-variants` cannot be combined".into());
+            }
+
+            if options.all_variants && options.submit_part.is_some() {
+                return Err("select a single variant to submit with `--variant`".into());
+            }
+        }
+
+        if is_timed && verbosity == Verbosity::Quiet {
+            return Err(
+                "`--quiet` only prints answers and cannot be combined with `--time`".into(),
+            );
+        }
+
+        Ok((app_args, verbosity))
     }
 }
 
@@ -76,17 +214,21 @@ fn main() {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
-        Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
-            AppArguments::Solve {
-                day,
-                release,
-                time,
-                submit,
-            } => solve::handle(day, release, time, submit),
-        },
+        Ok((args, verbosity)) => {
+            output::set_verbosity(verbosity);
+
+            match args {
+                AppArguments::All { options } => all::handle(&options),
+                AppArguments::Download { day } => download::handle(day),
+                AppArguments::Read { day } => read::handle(day),
+                AppArguments::Inspect { day } => inspect::handle(day),
+                AppArguments::Scaffold { day } => scaffold::handle(day),
+                AppArguments::Solve { day, options } => solve::handle(day, &options),
+                AppArguments::Minimize { day, options } => minimize::handle(day, &options),
+                AppArguments::Analyze { day, options } => analyze::handle(day, &options),
+                AppArguments::Compare { options } => compare::handle(&options),
+                AppArguments::Watch { day, options } => watch::handle(day, &options),
+            }
+        }
     };
 }
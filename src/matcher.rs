@@ -0,0 +1,240 @@
+/// Multi-pattern string matching (Aho–Corasick) that reports overlapping matches, e.g. both `one` and `eight` in `oneight`.
+use std::collections::VecDeque;
+
+/// A single occurrence of a pattern in a haystack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    /// Index of the matched pattern, in the order the patterns were passed to [`MultiMatcher::new`].
+    pub pattern: usize,
+    /// Byte offset of the first byte of the match.
+    pub start: usize,
+    /// Byte offset one past the last byte of the match.
+    pub end: usize,
+}
+
+const ROOT: usize = 0;
+
+struct State {
+    /// Transitions for every possible byte, with failure links already resolved.
+    next: [u32; 256],
+    /// Patterns that end in this state, including those reachable via failure links.
+    outputs: Vec<usize>,
+}
+
+impl State {
+    fn new() -> Self {
+        Self {
+            next: [u32::MAX; 256],
+            outputs: vec![],
+        }
+    }
+}
+
+/// An automaton that matches many patterns at once in time linear to the haystack.
+pub struct MultiMatcher {
+    states: Vec<State>,
+    lengths: Vec<usize>,
+}
+
+impl MultiMatcher {
+    /// Builds a matcher for the given patterns. Empty patterns never match.
+    pub fn new<P: AsRef<[u8]>>(patterns: impl IntoIterator<Item = P>) -> Self {
+        let mut states = vec![State::new()];
+        let mut lengths = vec![];
+
+        for (idx, pattern) in patterns.into_iter().enumerate() {
+            let pattern = pattern.as_ref();
+            lengths.push(pattern.len());
+
+            if pattern.is_empty() {
+                continue;
+            }
+
+            let mut current = ROOT;
+            for &byte in pattern {
+                let next = states[current].next[byte as usize];
+                current = if next == u32::MAX {
+                    states.push(State::new());
+                    let id = states.len() - 1;
+                    states[current].next[byte as usize] = id as u32;
+                    id
+                } else {
+                    next as usize
+                };
+            }
+            states[current].outputs.push(idx);
+        }
+
+        // resolve failure links breadth-first, turning the trie into a complete automaton.
+        let mut fail = vec![ROOT; states.len()];
+        let mut queue = VecDeque::new();
+
+        for byte in 0..256 {
+            let next = states[ROOT].next[byte];
+            if next == u32::MAX {
+                states[ROOT].next[byte] = ROOT as u32;
+            } else {
+                queue.push_back(next as usize);
+            }
+        }
+
+        while let Some(state) = queue.pop_front() {
+            let inherited = states[fail[state]].outputs.clone();
+            states[state].outputs.extend(inherited);
+
+            for byte in 0..256 {
+                let next = states[state].next[byte];
+                let fallback = states[fail[state]].next[byte];
+
+                if next == u32::MAX {
+                    states[state].next[byte] = fallback;
+                } else {
+                    fail[next as usize] = fallback as usize;
+                    queue.push_back(next as usize);
+                }
+            }
+        }
+
+        Self { states, lengths }
+    }
+
+    /// Iterates over all matches, including overlapping ones, ordered by their end position.
+    pub fn find_overlapping<'a>(&'a self, haystack: &'a str) -> impl Iterator<Item = Match> + 'a {
+        haystack
+            .bytes()
+            .enumerate()
+            .scan(ROOT, |state, (idx, byte)| {
+                *state = self.states[*state].next[byte as usize] as usize;
+                Some((idx + 1, *state))
+            })
+            .flat_map(move |(end, state)| {
+                self.states[state]
+                    .outputs
+                    .iter()
+                    .map(move |&pattern| Match {
+                        pattern,
+                        start: end - self.lengths[pattern],
+                        end,
+                    })
+            })
+    }
+
+    /// Returns the match that starts first. Ties are resolved in favour of the longer match.
+    pub fn leftmost(&self, haystack: &str) -> Option<Match> {
+        let longest = self.lengths.iter().copied().max().unwrap_or(0);
+        let mut best: Option<Match> = None;
+
+        for m in self.find_overlapping(haystack) {
+            if let Some(b) = best {
+                // no match ending here or later can start before the current best.
+                if m.end > b.start + longest {
+                    break;
+                }
+            }
+
+            if best.is_none_or(|b| (m.start, b.end) < (b.start, m.end)) {
+                best = Some(m);
+            }
+        }
+
+        best
+    }
+
+    /// Returns the match that ends last. Ties are resolved in favour of the longer match.
+    pub fn rightmost(&self, haystack: &str) -> Option<Match> {
+        self.find_overlapping(haystack).reduce(|b, m| {
+            if (m.end, b.start) > (b.end, m.start) {
+                m
+            } else {
+                b
+            }
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Match, MultiMatcher};
+
+    fn brute_force(patterns: &[&str], haystack: &str) -> Vec<Match> {
+        let mut matches = vec![];
+        for end in 1..=haystack.len() {
+            for (pattern, p) in patterns.iter().enumerate() {
+                if !p.is_empty() && haystack[..end].ends_with(p) {
+                    matches.push(Match {
+                        pattern,
+                        start: end - p.len(),
+                        end,
+                    });
+                }
+            }
+        }
+        matches
+    }
+
+    #[test]
+    fn finds_overlapping_matches() {
+        let matcher = MultiMatcher::new(["one", "eight", "two"]);
+        let matches = matcher.find_overlapping("twoneight").collect::<Vec<_>>();
+
+        assert_eq!(
+            matches,
+            vec![
+                Match {
+                    pattern: 2,
+                    start: 0,
+                    end: 3
+                },
+                Match {
+                    pattern: 0,
+                    start: 2,
+                    end: 5
+                },
+                Match {
+                    pattern: 1,
+                    start: 4,
+                    end: 9
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn leftmost_and_rightmost() {
+        let matcher = MultiMatcher::new(["1", "one", "eight", "eighty"]);
+
+        assert_eq!(matcher.leftmost("xoneighty1").map(|m| m.pattern), Some(1));
+        assert_eq!(matcher.rightmost("xoneighty1").map(|m| m.pattern), Some(0));
+        assert_eq!(matcher.rightmost("xoneighty").map(|m| m.pattern), Some(3));
+        assert_eq!(matcher.leftmost("eighty").map(|m| m.pattern), Some(3));
+        assert_eq!(matcher.leftmost("nothing here"), None);
+        assert_eq!(matcher.rightmost(""), None);
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let patterns = ["a", "ab", "bab", "bc", "bca", "c", "caa", "", "abcabc"];
+        let matcher = MultiMatcher::new(patterns);
+
+        for haystack in ["abccab", "bababcabcabca", "caaab", "", "xyz", "abcabcabc"] {
+            let mut expected = brute_force(&patterns, haystack);
+            let mut actual = matcher.find_overlapping(haystack).collect::<Vec<_>>();
+            expected.sort_by_key(|m| (m.end, m.pattern));
+            actual.sort_by_key(|m| (m.end, m.pattern));
+            assert_eq!(actual, expected, "haystack: {haystack}");
+
+            let leftmost = expected
+                .iter()
+                .min_by_key(|m| (m.start, usize::MAX - m.end));
+            let rightmost = expected
+                .iter()
+                .max_by_key(|m| (m.end, usize::MAX - m.start));
+            assert_eq!(matcher.leftmost(haystack).as_ref(), leftmost);
+            assert_eq!(matcher.rightmost(haystack).as_ref(), rightmost);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
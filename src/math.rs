@@ -0,0 +1,164 @@
+/// Exact integer math helpers.
+use std::ops::RangeInclusive;
+
+/// Returns all integers `x` for which `a*x² + b*x + c < 0`, or [`None`] if there are none.
+///
+/// `a` must be positive, which makes the solution a single bounded range.
+/// Other integer quadratic inequalities can be rewritten into this form:
+///  - `f(x) > 0` with `a < 0` is `-f(x) < 0`.
+///  - `f(x) <= 0` is `f(x) - 1 < 0`, since `f(x)` is an integer.
+///
+/// The result is exact; no floating point math is involved.
+///
+/// # Panics
+/// Panics if `a` is not positive or if the discriminant `b² - 4ac` does not fit into an [`i128`].
+pub fn quadratic_negative_range(a: i128, b: i128, c: i128) -> Option<RangeInclusive<i128>> {
+    assert!(a > 0, "the leading coefficient must be positive");
+
+    let discriminant = b
+        .checked_mul(b)
+        .zip(a.checked_mul(c).and_then(|ac| ac.checked_mul(4)))
+        .and_then(|(bb, ac4)| bb.checked_sub(ac4))
+        .expect("discriminant overflows i128");
+
+    // the minimum of the parabola is `-discriminant / 4a`, so it only dips below zero for a positive discriminant.
+    if discriminant <= 0 {
+        return None;
+    }
+
+    let f = |x: i128| (a * x + b) * x + c;
+    let root = discriminant.isqrt();
+
+    // the estimates are at most one off the exact bounds since `root` is the floored square root.
+    let mut lo = (-b - root).div_euclid(2 * a);
+    let mut hi = (-b + root).div_euclid(2 * a) + 1;
+
+    while f(lo) >= 0 && lo <= hi {
+        lo += 1;
+    }
+    while f(lo - 1) < 0 {
+        lo -= 1;
+    }
+    while f(hi) >= 0 && hi >= lo {
+        hi -= 1;
+    }
+    while f(hi + 1) < 0 {
+        hi += 1;
+    }
+
+    (lo <= hi).then_some(lo..=hi)
+}
+
+/// Counts the hold times `t` in `0..=time` for which a boat that travels `t * (time - t)` beats `record`.
+///
+/// Computes in [`u128`] and is exact for every [`u64`] input.
+pub fn race_wins(time: u64, record: u64) -> u64 {
+    let (time, record) = (u128::from(time), u128::from(record));
+    let beats = |t: u128| t * (time - t) > record;
+
+    // t * (time - t) > record  <=>  t² - time * t + record < 0
+    let Some(discriminant) = (time * time).checked_sub(4 * record) else {
+        return 0;
+    };
+
+    let root = discriminant.isqrt();
+    let mut lo = (time - root) / 2;
+    let mut hi = ((time + root) / 2 + 1).min(time);
+
+    while lo <= hi && !beats(lo) {
+        lo += 1;
+    }
+    while lo > 0 && beats(lo - 1) {
+        lo -= 1;
+    }
+    while hi >= lo && !beats(hi) {
+        hi -= 1;
+    }
+    while hi < time && beats(hi + 1) {
+        hi += 1;
+    }
+
+    if lo > hi {
+        return 0;
+    }
+
+    // at most `time + 1` values, which always fits a u64.
+    (hi - lo + 1) as u64
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{quadratic_negative_range, race_wins};
+
+    fn brute_force_race(time: u64, record: u64) -> u64 {
+        (0..=time).filter(|t| t * (time - t) > record).count() as u64
+    }
+
+    fn brute_force_quadratic(a: i128, b: i128, c: i128) -> Option<(i128, i128)> {
+        let xs = (-200..=200)
+            .filter(|x| a * x * x + b * x + c < 0)
+            .collect::<Vec<_>>();
+        Some((*xs.first()?, *xs.last()?))
+    }
+
+    #[test]
+    fn race_wins_matches_brute_force() {
+        for time in 0..=60 {
+            for record in 0..=(time * time / 4 + 2) {
+                assert_eq!(
+                    race_wins(time, record),
+                    brute_force_race(time, record),
+                    "time: {time}, record: {record}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn race_wins_examples() {
+        assert_eq!(race_wins(7, 9), 4);
+        assert_eq!(race_wins(15, 40), 8);
+        assert_eq!(race_wins(30, 200), 9);
+        assert_eq!(race_wins(71530, 940200), 71503);
+    }
+
+    #[test]
+    fn race_wins_at_u64_scale() {
+        // t * (time - t) > 0 holds for every t except 0 and time.
+        assert_eq!(race_wins(u64::MAX, 0), u64::MAX - 1);
+        // only t in {0, 1, time - 1, time} travel less than u64::MAX.
+        assert_eq!(race_wins(u64::MAX - 1, u64::MAX), u64::MAX - 4);
+        // 4294967295 * 4294967295 is the best possible distance for this time.
+        assert_eq!(race_wins(8589934590, 18446744065119617025), 0);
+        assert_eq!(race_wins(8589934590, 18446744065119617024), 1);
+    }
+
+    #[test]
+    fn quadratic_matches_brute_force() {
+        for a in 1..=5 {
+            for b in -30..=30 {
+                for c in -60..=60 {
+                    assert_eq!(
+                        quadratic_negative_range(a, b, c).map(|r| (*r.start(), *r.end())),
+                        brute_force_quadratic(a, b, c),
+                        "a: {a}, b: {b}, c: {c}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn quadratic_at_i128_scale() {
+        // x² - 10^36 < 0 for |x| < 10^18.
+        let bound = 10_i128.pow(18);
+        assert_eq!(
+            quadratic_negative_range(1, 0, -bound * bound),
+            Some(-bound + 1..=bound - 1)
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,356 @@
+//! Recognition of the letters that some puzzles draw as their answer, in the 4×6 and 6×10 Advent of Code fonts.
+//! Lit pixels are `#` or `█`, anything else is unlit. Letters are separated by at least one unlit column.
+
+/// Glyphs of the small font, 6 rows high. Most are 4 columns wide, `I` is 3 and `Y` is 5.
+const SMALL_FONT: [(char, &[&str]); 18] = [
+    ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', &["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', &["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Glyphs of the large font, 10 rows high and 6 columns wide.
+#[rustfmt::skip]
+const LARGE_FONT: [(char, &[&str]); 15] = [
+    ('A', &[
+        "..##..",
+        ".#..#.",
+        "#....#",
+        "#....#",
+        "#....#",
+        "######",
+        "#....#",
+        "#....#",
+        "#....#",
+        "#....#",
+    ]),
+    ('B', &[
+        "#####.",
+        "#....#",
+        "#....#",
+        "#....#",
+        "#####.",
+        "#....#",
+        "#....#",
+        "#....#",
+        "#....#",
+        "#####.",
+    ]),
+    ('C', &[
+        ".####.",
+        "#....#",
+        "#.....",
+        "#.....",
+        "#.....",
+        "#.....",
+        "#.....",
+        "#.....",
+        "#....#",
+        ".####.",
+    ]),
+    ('E', &[
+        "######",
+        "#.....",
+        "#.....",
+        "#.....",
+        "#####.",
+        "#.....",
+        "#.....",
+        "#.....",
+        "#.....",
+        "######",
+    ]),
+    ('F', &[
+        "######",
+        "#.....",
+        "#.....",
+        "#.....",
+        "#####.",
+        "#.....",
+        "#.....",
+        "#.....",
+        "#.....",
+        "#.....",
+    ]),
+    ('G', &[
+        ".####.",
+        "#....#",
+        "#.....",
+        "#.....",
+        "#.....",
+        "#..###",
+        "#....#",
+        "#....#",
+        "#...##",
+        ".###.#",
+    ]),
+    ('H', &[
+        "#....#",
+        "#....#",
+        "#....#",
+        "#....#",
+        "######",
+        "#....#",
+        "#....#",
+        "#....#",
+        "#....#",
+        "#....#",
+    ]),
+    ('J', &[
+        "...###",
+        "....#.",
+        "....#.",
+        "....#.",
+        "....#.",
+        "....#.",
+        "....#.",
+        "#...#.",
+        "#...#.",
+        ".###..",
+    ]),
+    ('K', &[
+        "#....#",
+        "#...#.",
+        "#..#..",
+        "#.#...",
+        "##....",
+        "##....",
+        "#.#...",
+        "#..#..",
+        "#...#.",
+        "#....#",
+    ]),
+    ('L', &[
+        "#.....",
+        "#.....",
+        "#.....",
+        "#.....",
+        "#.....",
+        "#.....",
+        "#.....",
+        "#.....",
+        "#.....",
+        "######",
+    ]),
+    ('N', &[
+        "#....#",
+        "##...#",
+        "##...#",
+        "#.#..#",
+        "#.#..#",
+        "#..#.#",
+        "#..#.#",
+        "#...##",
+        "#...##",
+        "#....#",
+    ]),
+    ('P', &[
+        "#####.",
+        "#....#",
+        "#....#",
+        "#....#",
+        "#####.",
+        "#.....",
+        "#.....",
+        "#.....",
+        "#.....",
+        "#.....",
+    ]),
+    ('R', &[
+        "#####.",
+        "#....#",
+        "#....#",
+        "#....#",
+        "#####.",
+        "#..#..",
+        "#...#.",
+        "#...#.",
+        "#....#",
+        "#....#",
+    ]),
+    ('X', &[
+        "#....#",
+        "#....#",
+        ".#..#.",
+        ".#..#.",
+        "..##..",
+        "..##..",
+        ".#..#.",
+        ".#..#.",
+        "#....#",
+        "#....#",
+    ]),
+    ('Z', &[
+        "######",
+        ".....#",
+        ".....#",
+        "....#.",
+        "...#..",
+        "..#...",
+        ".#....",
+        "#.....",
+        "#.....",
+        "######",
+    ]),
+];
+
+/// A grid of pixels, one row per line.
+type Pixels = Vec<Vec<bool>>;
+
+/// Decodes the letters drawn in `art`, e.g. the multi-line answer of a part.
+/// Returns [`None`] if `art` does not match the height of a font or contains a glyph that is not a known letter.
+#[must_use]
+pub fn decode(art: &str) -> Option<String> {
+    let pixels = parse(art);
+    let font: &[(char, &[&str])] = match pixels.len() {
+        6 => &SMALL_FONT,
+        10 => &LARGE_FONT,
+        _ => return None,
+    };
+
+    let glyphs = split_glyphs(&pixels);
+    if glyphs.is_empty() {
+        return None;
+    }
+
+    glyphs
+        .iter()
+        .map(|glyph| {
+            font.iter()
+                .find(|(_, known)| trim_columns(&parse(&known.join("\n"))) == *glyph)
+                .map(|(letter, _)| *letter)
+        })
+        .collect()
+}
+
+/// Parses art into pixels, dropping unlit rows above and below the letters.
+fn parse(art: &str) -> Pixels {
+    let rows = art
+        .lines()
+        .map(|line| {
+            line.chars()
+                .map(|c| c == '#' || c == '█')
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let is_lit = |row: &&Vec<bool>| row.iter().any(|lit| *lit);
+    let first = rows
+        .iter()
+        .position(|row| is_lit(&row))
+        .unwrap_or(rows.len());
+    let last = rows
+        .iter()
+        .rposition(|row| is_lit(&row))
+        .map_or(first, |last| last + 1);
+
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    rows[first..last]
+        .iter()
+        .map(|row| {
+            let mut row = row.clone();
+            row.resize(width, false);
+            row
+        })
+        .collect()
+}
+
+/// Splits pixels into glyphs at unlit columns. Every glyph is trimmed to its lit columns.
+fn split_glyphs(pixels: &Pixels) -> Vec<Pixels> {
+    let width = pixels.first().map_or(0, Vec::len);
+    let is_lit = |col: usize| pixels.iter().any(|row| row[col]);
+
+    let mut glyphs = vec![];
+    let mut start = None;
+
+    for col in 0..=width {
+        match (start, col < width && is_lit(col)) {
+            (None, true) => start = Some(col),
+            (Some(first), false) => {
+                glyphs.push(pixels.iter().map(|row| row[first..col].to_vec()).collect());
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    glyphs
+}
+
+/// Drops unlit columns left and right of a glyph of the font.
+fn trim_columns(pixels: &Pixels) -> Pixels {
+    let width = pixels.first().map_or(0, Vec::len);
+    let is_lit = |col: usize| pixels.iter().any(|row| row[col]);
+
+    let first = (0..width).find(|col| is_lit(*col)).unwrap_or(0);
+    let last = (0..width)
+        .rfind(|col| is_lit(*col))
+        .map_or(first, |col| col + 1);
+
+    pixels.iter().map(|row| row[first..last].to_vec()).collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decode, LARGE_FONT, SMALL_FONT};
+
+    #[test]
+    fn decodes_small_font() {
+        let art = "\
+####.####
+#.......#
+###....#.
+#.....#..
+#....#...
+####.####";
+        assert_eq!(decode(art), Some("EZ".into()));
+
+        // narrow and wide letters, other pixel characters and surrounding blank rows.
+        let art = "\n███  █   █\n █   █   █\n █    █ █ \n █     █  \n █     █  \n███    █  \n\n";
+        assert_eq!(decode(art), Some("IY".into()));
+    }
+
+    #[test]
+    fn decodes_every_letter() {
+        for font in [&SMALL_FONT[..], &LARGE_FONT[..]] {
+            let height = font[0].1.len();
+            let rows = (0..height)
+                .map(|row| {
+                    font.iter()
+                        .map(|(_, glyph)| glyph[row])
+                        .collect::<Vec<_>>()
+                        .join("..")
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let letters = font.iter().map(|(letter, _)| *letter).collect::<String>();
+            assert_eq!(decode(&rows), Some(letters));
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_glyphs() {
+        assert_eq!(decode("#.#\n.#.\n#.#"), None);
+        assert_eq!(decode("#..#\n#..#\n####\n#..#\n#..#\n#.##"), None);
+        assert_eq!(decode("......\n......"), None);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,203 @@
+/// Shared parsing helpers for solutions.
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::Day;
+
+/// An error that points at the offending text of a puzzle input.
+///
+/// # Display
+/// The error displays as a diagnostic that quotes the offending input line.
+///
+/// ```text
+/// day 04: invalid number `x1` at line 3, column 9
+///   |
+/// 3 | Card 3: x1 21 53 59 44 | 69 82 63 72 16 21 14  1
+///   |         ^^
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The day whose input failed to parse.
+    pub day: Day,
+    /// One-based line number of the offending text, `0` if unknown.
+    pub line: usize,
+    /// One-based column (in characters) of the offending text, `0` if unknown.
+    pub column: usize,
+    /// The offending text itself.
+    pub text: String,
+    /// A description of what was expected.
+    pub message: String,
+    source_line: String,
+}
+
+impl ParseError {
+    /// Creates a [`ParseError`] for `text` without location information.
+    pub fn new(day: Day, text: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            day,
+            line: 0,
+            column: 0,
+            text: text.into(),
+            message: message.into(),
+            source_line: String::new(),
+        }
+    }
+
+    /// Creates a [`ParseError`] for `token`, which must be a sub-slice of `source`.
+    /// Line and column are derived from the position of `token` in `source`.
+    pub fn locate(day: Day, source: &str, token: &str, message: impl Into<String>) -> Self {
+        let mut err = Self::new(day, token, message);
+
+        let start = source.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize).wrapping_sub(start);
+
+        if offset > source.len() || offset + token.len() > source.len() {
+            return err;
+        }
+
+        let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[offset..]
+            .find('\n')
+            .map_or(source.len(), |i| offset + i);
+
+        err.line = source[..offset].matches('\n').count() + 1;
+        err.column = source[line_start..offset].chars().count() + 1;
+        err.source_line = source[line_start..line_end].trim_end_matches('\r').into();
+        err
+    }
+
+    /// Moves an error that was located within a single line of the input to the given one-based line.
+    #[must_use]
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "day {}: {} `{}`", self.day, self.message, self.text)?;
+
+        if self.line == 0 {
+            return Ok(());
+        }
+
+        if self.column == 0 {
+            return write!(f, " at line {}", self.line);
+        }
+
+        write!(f, " at line {}, column {}", self.line, self.column)?;
+
+        if self.source_line.is_empty() {
+            return Ok(());
+        }
+
+        let gutter = self.line.to_string().len();
+        let underline = "^".repeat(self.text.chars().count().max(1));
+
+        writeln!(f)?;
+        writeln!(f, "{:gutter$} |", "")?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(
+            f,
+            "{:gutter$} | {:pad$}{underline}",
+            "",
+            "",
+            pad = self.column - 1
+        )
+    }
+}
+
+/// Parses `token`, a sub-slice of `source`, into a number (or any other [`FromStr`] type).
+pub fn number<T: FromStr>(day: Day, source: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::locate(day, source, token, "invalid number"))
+}
+
+/// Splits `token`, a sub-slice of `source`, once at `delimiter`.
+/// Returns an error pointing at `token` if the delimiter is missing.
+pub fn split_once<'a>(
+    day: Day,
+    source: &str,
+    token: &'a str,
+    delimiter: char,
+) -> Result<(&'a str, &'a str), ParseError> {
+    token
+        .split_once(delimiter)
+        .ok_or_else(|| ParseError::locate(day, source, token, format!("missing `{delimiter}` in")))
+}
+
+/// Parses every line of `input` into `T`, attaching line numbers to errors.
+pub fn lines<T>(input: &str) -> impl Iterator<Item = Result<T, ParseError>> + '_
+where
+    T: FromStr<Err = ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| line.parse().map_err(|e: ParseError| e.on_line(idx + 1)))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{lines, number, ParseError};
+    use crate::day;
+
+    struct Value(u32);
+
+    impl std::str::FromStr for Value {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let (_, value) = s.split_once(':').unwrap();
+            number(day!(1), s, value.trim()).map(Value)
+        }
+    }
+
+    #[test]
+    fn locates_token_in_multiline_source() {
+        let source = "a 1\nb 22 x3\nc";
+        let token = &source[9..11];
+        let err = ParseError::locate(day!(4), source, token, "invalid number");
+
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, 6);
+        assert_eq!(err.text, "x3");
+    }
+
+    #[test]
+    fn foreign_token_has_no_location() {
+        let (source, token) = (String::from("abc"), String::from("def"));
+        let err = ParseError::locate(day!(4), &source, &token, "invalid number");
+        assert_eq!(err.line, 0);
+        assert_eq!(err.to_string(), "day 04: invalid number `def`");
+        assert_eq!(err, ParseError::new(day!(4), "def", "invalid number"));
+    }
+
+    #[test]
+    fn formats_diagnostic() {
+        let input = "a: 1\nb: 2\nc: x3";
+        let err = lines::<Value>(input)
+            .collect::<Result<Vec<_>, _>>()
+            .err()
+            .unwrap();
+
+        let expected = [
+            "day 01: invalid number `x3` at line 3, column 4",
+            "  |",
+            "3 | c: x3",
+            "  |    ^^",
+        ]
+        .join("\n");
+
+        assert_eq!(err.to_string(), expected);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,155 @@
+/// Opt-in heap allocation tracking for solutions.
+/// Solutions install [`CountingAllocator`] as their global allocator when the `track_allocs` feature is enabled.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+static BASELINE_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// A global allocator that wraps the system allocator and counts allocations.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn record(added: usize, removed: usize) {
+        INSTALLED.store(true, Ordering::Relaxed);
+
+        if added > 0 {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            ALLOCATED_BYTES.fetch_add(added, Ordering::Relaxed);
+        }
+
+        let live = if added >= removed {
+            LIVE_BYTES.fetch_add(added - removed, Ordering::Relaxed) + (added - removed)
+        } else {
+            LIVE_BYTES.fetch_sub(removed - added, Ordering::Relaxed) - (removed - added)
+        };
+
+        PEAK_LIVE_BYTES.fetch_max(live, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::record(0, layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            // a reallocation counts as a new allocation of `new_size` bytes.
+            Self::record(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+/// Allocation statistics since the last call to [`reset`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations and reallocations.
+    pub allocations: usize,
+    /// Total number of bytes allocated.
+    pub bytes: usize,
+    /// Highest number of bytes that were live at once, not counting memory that was live before the reset.
+    pub peak_bytes: usize,
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} total, {} peak",
+            self.allocations,
+            format_bytes(self.bytes),
+            format_bytes(self.peak_bytes)
+        )
+    }
+}
+
+/// Starts a new measurement.
+pub fn reset() {
+    let live = LIVE_BYTES.load(Ordering::Relaxed);
+    ALLOCATIONS.store(0, Ordering::Relaxed);
+    ALLOCATED_BYTES.store(0, Ordering::Relaxed);
+    BASELINE_BYTES.store(live, Ordering::Relaxed);
+    PEAK_LIVE_BYTES.store(live, Ordering::Relaxed);
+}
+
+/// Returns the statistics since the last call to [`reset`], or [`None`] if [`CountingAllocator`] is not installed.
+pub fn stats() -> Option<AllocStats> {
+    if !INSTALLED.load(Ordering::Relaxed) {
+        return None;
+    }
+
+    Some(AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        bytes: ALLOCATED_BYTES.load(Ordering::Relaxed),
+        peak_bytes: PEAK_LIVE_BYTES
+            .load(Ordering::Relaxed)
+            .saturating_sub(BASELINE_BYTES.load(Ordering::Relaxed)),
+    })
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, AllocStats};
+
+    #[test]
+    fn formats_stats() {
+        let stats = AllocStats {
+            allocations: 3,
+            bytes: 3 * 1024 + 512,
+            peak_bytes: 100,
+        };
+        assert_eq!(stats.to_string(), "3 allocs, 3.5 KiB total, 100 B peak");
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1024), "1.0 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MiB");
+    }
+}
@@ -0,0 +1,531 @@
+/// Measures how a solution part scales by running it on generated inputs of growing size.
+/// The timings are fitted to common complexity classes and plotted on log-log axes, in the terminal or as SVG.
+use std::fmt::Display;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use std::{env, fs, process};
+
+use crate::template::output;
+use crate::template::runner::{self, Outcome, PartResult};
+use crate::Day;
+
+/// Sizes are doubled from this one unless `--sizes` is passed.
+const MIN_SIZE: usize = 16;
+const MAX_SIZE: usize = 1 << 20;
+
+/// No larger sizes are tried once a single run takes longer than this.
+const MAX_RUN_TIME: Duration = Duration::from_secs(1);
+
+/// Every size is run repeatedly for at least this long, and the fastest run is kept.
+const MIN_MEASURE_TIME: Duration = Duration::from_millis(100);
+const MIN_RUNS: u32 = 3;
+
+const PLOT_WIDTH: usize = 60;
+const PLOT_HEIGHT: usize = 15;
+
+/// Generates a puzzle input of the given size, e.g. the number of lines or the length of a line.
+pub type Generator = fn(usize) -> String;
+
+/// Complexity classes that timings are fitted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Complexity {
+    Constant,
+    Logarithmic,
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Cubic,
+    Exponential,
+}
+
+impl Complexity {
+    const ALL: [Complexity; 7] = [
+        Complexity::Constant,
+        Complexity::Logarithmic,
+        Complexity::Linear,
+        Complexity::Linearithmic,
+        Complexity::Quadratic,
+        Complexity::Cubic,
+        Complexity::Exponential,
+    ];
+
+    /// The natural logarithm of the growth function at size `n`.
+    fn ln_growth(self, n: f64) -> f64 {
+        let ln_n = n.max(2.0).ln();
+        match self {
+            Complexity::Constant => 0.0,
+            Complexity::Logarithmic => ln_n.ln(),
+            Complexity::Linear => ln_n,
+            Complexity::Linearithmic => ln_n + ln_n.ln(),
+            Complexity::Quadratic => 2.0 * ln_n,
+            Complexity::Cubic => 3.0 * ln_n,
+            Complexity::Exponential => n * std::f64::consts::LN_2,
+        }
+    }
+}
+
+impl Display for Complexity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Complexity::Constant => "O(1)",
+            Complexity::Logarithmic => "O(log n)",
+            Complexity::Linear => "O(n)",
+            Complexity::Linearithmic => "O(n log n)",
+            Complexity::Quadratic => "O(n²)",
+            Complexity::Cubic => "O(n³)",
+            Complexity::Exponential => "O(2ⁿ)",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// The fastest run of a part on an input of the given size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    pub size: usize,
+    pub duration: Duration,
+}
+
+/// The result of fitting samples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fit {
+    /// Slope of the timings on log-log axes, i.e. `k` in `t ~ n^k`.
+    pub exponent: f64,
+    /// The complexity class that fits the timings best.
+    pub complexity: Complexity,
+    /// The constant factor of the best fitting class, `t ≈ e^ln_factor * f(n)`.
+    ln_factor: f64,
+}
+
+impl Fit {
+    /// The duration predicted for size `n`, in seconds.
+    fn predict(&self, n: f64) -> f64 {
+        (self.ln_factor + self.complexity.ln_growth(n)).exp()
+    }
+}
+
+/// Fits samples to the complexity classes via least squares on log-log axes.
+/// Returns [`None`] if there are fewer than two distinct sizes.
+pub fn fit(samples: &[Sample]) -> Option<Fit> {
+    let points = samples
+        .iter()
+        .map(|s| (s.size as f64, s.duration.as_secs_f64().max(1e-9).ln()))
+        .collect::<Vec<_>>();
+
+    let xs = points.iter().map(|(n, _)| n.ln()).collect::<Vec<_>>();
+    let ys = points.iter().map(|(_, y)| *y).collect::<Vec<_>>();
+
+    let mean_x = xs.iter().sum::<f64>() / xs.len() as f64;
+    let mean_y = ys.iter().sum::<f64>() / ys.len() as f64;
+    let var_x = xs.iter().map(|x| (x - mean_x).powi(2)).sum::<f64>();
+
+    if samples.len() < 2 || var_x == 0.0 {
+        return None;
+    }
+
+    let cov = xs
+        .iter()
+        .zip(&ys)
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum::<f64>();
+
+    // for every class, the best constant factor in log space is the mean offset between timings and growth.
+    let (complexity, ln_factor, _) = Complexity::ALL
+        .iter()
+        .map(|&complexity| {
+            let offsets = points
+                .iter()
+                .map(|(n, y)| y - complexity.ln_growth(*n))
+                .collect::<Vec<_>>();
+            let ln_factor = offsets.iter().sum::<f64>() / offsets.len() as f64;
+            let residual = offsets.iter().map(|o| (o - ln_factor).powi(2)).sum::<f64>();
+            (complexity, ln_factor, residual)
+        })
+        .min_by(|a, b| a.2.total_cmp(&b.2))?;
+
+    Some(Fit {
+        exponent: cov / var_x,
+        complexity,
+        ln_factor,
+    })
+}
+
+/// Parses a comma-separated list of sizes, e.g. `100,1000,10000`.
+pub fn parse_sizes(s: &str) -> Result<Vec<usize>, String> {
+    let sizes = s
+        .split(',')
+        .map(|size| size.trim().parse::<usize>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| format!("expecting sizes like `100,1000,10000`, found `{s}`"))?;
+
+    if sizes.len() < 2 {
+        return Err("expecting at least two sizes".into());
+    }
+
+    Ok(sizes)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Whether `--analyze` was passed to the current solution binary.
+pub fn is_requested() -> bool {
+    env::args().any(|x| x == "--analyze")
+}
+
+/// Analyzes one part of a solution with the sizes and SVG directory passed to the current solution binary.
+pub fn analyze_part<R: PartResult>(
+    func: impl Fn(&str) -> R + Sync,
+    generator: Generator,
+    day: Day,
+    part: u8,
+    variant: &str,
+) {
+    let args: Vec<String> = env::args().collect();
+    let arg_value = |flag: &str| {
+        args.iter()
+            .position(|x| x == flag)
+            .and_then(|index| args.get(index + 1))
+    };
+
+    let sizes = match arg_value("--sizes").map(|s| parse_sizes(s)) {
+        Some(Ok(sizes)) => Some(sizes),
+        Some(Err(e)) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+        None => None,
+    };
+
+    let is_selected = args
+        .iter()
+        .any(|x| x == "--variant" || x == "--all-variants");
+    let part_str = if is_selected {
+        format!("Part {part} ({variant})")
+    } else {
+        format!("Part {part}")
+    };
+
+    let samples =
+        match runner::on_worker_thread(&part_str, || measure(&func, generator, sizes.as_deref())) {
+            Ok(samples) => samples,
+            Err(e) => {
+                println!("{part_str}: ⚠ failed");
+                println!("{e}");
+                process::exit(1);
+            }
+        };
+
+    let Some(fit) = fit(&samples) else {
+        println!("{part_str}: ✖ not enough samples to fit");
+        return;
+    };
+
+    println!(
+        "{part_str}: {} (exponent {:.2})",
+        output::bold(fit.complexity),
+        fit.exponent
+    );
+
+    if !output::is_quiet() {
+        println!("{}", plot(&samples, &fit));
+    }
+
+    if let Some(dir) = arg_value("--svg") {
+        let mut name = format!("{day}-part{part}");
+        if is_selected {
+            name.push_str(&format!("-{variant}"));
+        }
+        let path = Path::new(dir).join(name + ".svg");
+
+        if let Err(e) = fs::create_dir_all(dir).and_then(|()| fs::write(&path, svg(&samples, &fit)))
+        {
+            eprintln!("Failed to write \"{}\": {e}", path.display());
+            process::exit(1);
+        }
+
+        output::status(format!("Wrote plot to \"{}\".", path.display()));
+    }
+}
+
+/// Runs a part on generated inputs. Without explicit sizes, sizes are doubled until a run takes longer than [`MAX_RUN_TIME`].
+fn measure<R: PartResult>(
+    func: impl Fn(&str) -> R,
+    generator: Generator,
+    sizes: Option<&[usize]>,
+) -> Result<Vec<Sample>, String> {
+    let default_sizes = std::iter::successors(Some(MIN_SIZE), |size| Some(size * 2))
+        .take_while(|size| *size <= MAX_SIZE)
+        .collect::<Vec<_>>();
+
+    let mut samples = vec![];
+
+    for &size in sizes.unwrap_or(&default_sizes) {
+        let input = generator(size);
+        let mut fastest = Duration::MAX;
+        let mut runs = 0;
+        let timer = Instant::now();
+
+        while runs < MIN_RUNS || timer.elapsed() < MIN_MEASURE_TIME {
+            let run_timer = Instant::now();
+            let outcome = func(&input).into_outcome();
+            fastest = fastest.min(run_timer.elapsed());
+            runs += 1;
+
+            if let Outcome::Failed(e) = outcome {
+                return Err(format!("n = {size}: {e}"));
+            }
+
+            if fastest > MAX_RUN_TIME {
+                break;
+            }
+        }
+
+        output::detail(format!("  n = {size:<8} {fastest:.1?} @ {runs} samples"));
+        samples.push(Sample {
+            size,
+            duration: fastest,
+        });
+
+        if sizes.is_none() && fastest > MAX_RUN_TIME {
+            break;
+        }
+    }
+
+    Ok(samples)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Axes of a log-log plot.
+struct Axes {
+    x: (f64, f64),
+    y: (f64, f64),
+}
+
+impl Axes {
+    fn new(samples: &[Sample], fit: &Fit) -> Self {
+        let ln_sizes = samples.iter().map(|s| (s.size as f64).max(1.0).ln());
+        let ln_times = samples
+            .iter()
+            .map(|s| s.duration.as_secs_f64().max(1e-9).ln())
+            .chain(samples.iter().map(|s| fit.predict(s.size as f64).ln()));
+
+        let x = min_max(ln_sizes);
+        let y = min_max(ln_times);
+
+        Self { x, y }
+    }
+
+    /// Scales `value` from `range` to `0.0..=1.0`.
+    fn scale(value: f64, range: (f64, f64)) -> f64 {
+        if range.1 > range.0 {
+            (value - range.0) / (range.1 - range.0)
+        } else {
+            0.5
+        }
+    }
+
+    fn x(&self, size: f64) -> f64 {
+        Self::scale(size.max(1.0).ln(), self.x)
+    }
+
+    fn y(&self, seconds: f64) -> f64 {
+        Self::scale(seconds.max(1e-9).ln(), self.y)
+    }
+}
+
+fn min_max(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+        (min.min(v), max.max(v))
+    })
+}
+
+/// Plots samples (`●`) and the fitted class (`·`) on log-log axes.
+pub fn plot(samples: &[Sample], fit: &Fit) -> String {
+    let axes = Axes::new(samples, fit);
+    let mut grid = vec![vec![' '; PLOT_WIDTH]; PLOT_HEIGHT];
+
+    let to_cell = |x: f64, y: f64| {
+        let col = (x * (PLOT_WIDTH - 1) as f64).round() as usize;
+        let row = PLOT_HEIGHT - 1 - (y * (PLOT_HEIGHT - 1) as f64).round() as usize;
+        (row.min(PLOT_HEIGHT - 1), col.min(PLOT_WIDTH - 1))
+    };
+
+    for col in 0..PLOT_WIDTH {
+        let x = col as f64 / (PLOT_WIDTH - 1) as f64;
+        let size = (axes.x.0 + x * (axes.x.1 - axes.x.0)).exp();
+        let y = axes.y(fit.predict(size));
+        if (0.0..=1.0).contains(&y) {
+            let (row, col) = to_cell(x, y);
+            grid[row][col] = '·';
+        }
+    }
+
+    for sample in samples {
+        let (row, col) = to_cell(
+            axes.x(sample.size as f64),
+            axes.y(sample.duration.as_secs_f64()),
+        );
+        grid[row][col] = '●';
+    }
+
+    let top = format_seconds(axes.y.1.exp());
+    let bottom = format_seconds(axes.y.0.exp());
+    let label_width = top.chars().count().max(bottom.chars().count());
+
+    let mut lines = grid
+        .iter()
+        .enumerate()
+        .map(|(row, cells)| {
+            let label = match row {
+                0 => top.as_str(),
+                r if r == PLOT_HEIGHT - 1 => bottom.as_str(),
+                _ => "",
+            };
+            let tick = if label.is_empty() { '│' } else { '┤' };
+            let cells = cells.iter().collect::<String>();
+            format!("{label:>label_width$} {tick}{}", cells.trim_end())
+        })
+        .collect::<Vec<_>>();
+
+    let min_size = samples.iter().map(|s| s.size).min().unwrap_or(0);
+    let max_size = samples.iter().map(|s| s.size).max().unwrap_or(0);
+    let min_label = format!("n = {min_size}");
+    let max_label = max_size.to_string();
+    lines.push(format!("{:label_width$} └{}", "", "─".repeat(PLOT_WIDTH)));
+    lines.push(format!(
+        "{:label_width$}  {min_label}{max_label:>width$}",
+        "",
+        width = PLOT_WIDTH.saturating_sub(min_label.len())
+    ));
+
+    lines.join("\n")
+}
+
+/// Renders samples and the fitted class as an SVG image with log-log axes.
+pub fn svg(samples: &[Sample], fit: &Fit) -> String {
+    const WIDTH: f64 = 640.0;
+    const HEIGHT: f64 = 400.0;
+    const MARGIN: f64 = 60.0;
+
+    let axes = Axes::new(samples, fit);
+    let to_px = |x: f64, y: f64| {
+        (
+            MARGIN + x * (WIDTH - 2.0 * MARGIN),
+            HEIGHT - MARGIN - y * (HEIGHT - 2.0 * MARGIN),
+        )
+    };
+
+    let mut fitted = samples.iter().map(|s| s.size).collect::<Vec<_>>();
+    fitted.sort_unstable();
+    let fitted = fitted
+        .iter()
+        .map(|&size| {
+            let (x, y) = to_px(axes.x(size as f64), axes.y(fit.predict(size as f64)));
+            format!("{x:.1},{y:.1}")
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let points = samples
+        .iter()
+        .map(|s| {
+            let (x, y) = to_px(axes.x(s.size as f64), axes.y(s.duration.as_secs_f64()));
+            format!("  <circle cx=\"{x:.1}\" cy=\"{y:.1}\" r=\"4\" fill=\"#c0392b\"/>")
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let (x0, y0) = to_px(0.0, 0.0);
+    let (x1, y1) = to_px(1.0, 1.0);
+    let min_size = samples.iter().map(|s| s.size).min().unwrap_or(0);
+    let max_size = samples.iter().map(|s| s.size).max().unwrap_or(0);
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" font-family="sans-serif" font-size="12">
+  <rect width="100%" height="100%" fill="white"/>
+  <polyline points="{x0},{y1} {x0},{y0} {x1},{y0}" fill="none" stroke="black"/>
+  <text x="{x0}" y="{label_y}" text-anchor="middle">{min_size}</text>
+  <text x="{x1}" y="{label_y}" text-anchor="middle">{max_size}</text>
+  <text x="{center_x}" y="{label_y}" text-anchor="middle">input size (log)</text>
+  <text x="{tick_x}" y="{y0}" text-anchor="end">{bottom}</text>
+  <text x="{tick_x}" y="{y1}" text-anchor="end">{top}</text>
+  <text x="{center_x}" y="{title_y}" text-anchor="middle">{complexity}, exponent {exponent:.2}</text>
+  <polyline points="{fitted}" fill="none" stroke="#2980b9" stroke-dasharray="4 4"/>
+{points}
+</svg>
+"##,
+        label_y = y0 + 20.0,
+        center_x = WIDTH / 2.0,
+        tick_x = x0 - 6.0,
+        title_y = MARGIN / 2.0,
+        bottom = format_seconds(axes.y.0.exp()),
+        top = format_seconds(axes.y.1.exp()),
+        complexity = fit.complexity,
+        exponent = fit.exponent,
+    )
+}
+
+fn format_seconds(seconds: f64) -> String {
+    format!("{:.1?}", Duration::from_secs_f64(seconds))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fit, parse_sizes, plot, svg, Complexity, Sample};
+    use std::time::Duration;
+
+    fn samples(f: impl Fn(f64) -> f64) -> Vec<Sample> {
+        [16, 32, 64, 128, 256, 512, 1024]
+            .iter()
+            .map(|&size| Sample {
+                size,
+                duration: Duration::from_secs_f64(f(size as f64) * 1e-6),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fits_complexity_classes() {
+        let linear = fit(&samples(|n| 3.0 * n)).unwrap();
+        assert_eq!(linear.complexity, Complexity::Linear);
+        assert!((linear.exponent - 1.0).abs() < 0.01);
+
+        let quadratic = fit(&samples(|n| 0.5 * n * n)).unwrap();
+        assert_eq!(quadratic.complexity, Complexity::Quadratic);
+        assert!((quadratic.exponent - 2.0).abs() < 0.01);
+
+        let linearithmic = fit(&samples(|n| n * n.ln())).unwrap();
+        assert_eq!(linearithmic.complexity, Complexity::Linearithmic);
+
+        let constant = fit(&samples(|_| 5.0)).unwrap();
+        assert_eq!(constant.complexity, Complexity::Constant);
+
+        assert!(fit(&samples(|n| n)[..1]).is_none());
+    }
+
+    #[test]
+    fn parses_sizes() {
+        assert_eq!(parse_sizes("10, 100,1000"), Ok(vec![10, 100, 1000]));
+        assert!(parse_sizes("10").is_err());
+        assert!(parse_sizes("10,x").is_err());
+    }
+
+    #[test]
+    fn plots_samples() {
+        let samples = samples(|n| n * n);
+        let fit = fit(&samples).unwrap();
+
+        let plot = plot(&samples, &fit);
+        assert_eq!(plot.matches('●').count(), samples.len());
+        assert!(plot.lines().last().unwrap().trim().starts_with("n = 16"));
+
+        let svg = svg(&samples, &fit);
+        assert_eq!(svg.matches("<circle").count(), samples.len());
+        assert!(svg.contains("O(n²), exponent 2.00"));
+    }
+}
+
+/* -------------------------------------------------------------------------- */
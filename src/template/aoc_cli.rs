@@ -4,6 +4,7 @@ use std::{
     process::{Command, Output, Stdio},
 };
 
+use crate::template::output;
 use crate::Day;
 
 #[derive(Debug)]
@@ -67,11 +68,13 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
         day,
     );
 
-    let output = call_aoc_cli(&args)?;
-    println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-    Ok(output)
+    let result = call_aoc_cli(&args)?;
+    output::status("---");
+    output::status(format!("🎄 Successfully wrote input to \"{input_path}\"."));
+    output::status(format!(
+        "🎄 Successfully wrote puzzle to \"{puzzle_path}\"."
+    ));
+    Ok(result)
 }
 
 pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
@@ -100,6 +103,10 @@ fn get_year() -> Option<u16> {
 fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
+    if output::is_quiet() {
+        cmd_args.push("--quiet".into());
+    }
+
     if let Some(year) = get_year() {
         cmd_args.push("--year".into());
         cmd_args.push(year.to_string());
@@ -111,7 +118,7 @@ fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
 }
 
 fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
-    // println!("Calling >aoc with: {}", args.join(" "));
+    output::detail(format!("Calling >aoc with: {}", args.join(" ")));
     let output = Command::new("aoc")
         .args(args)
         .stdout(Stdio::inherit())
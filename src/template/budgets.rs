@@ -0,0 +1,218 @@
+/// Performance budgets that `all --time --enforce-budgets` checks the timings of all solutions against.
+/// Budgets are read from `budgets.txt`, which sets defaults for the whole year, every day and every part, and may override them for single days.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs;
+use std::time::Duration;
+
+use crate::template::duration::{from_nanos, parse_duration};
+use crate::template::readme_benchmarks::Timings;
+use crate::Day;
+
+pub const BUDGETS_PATH: &str = "budgets.txt";
+
+/// Budgets of a single day that override the defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct DayBudgets {
+    total: Option<Duration>,
+    parts: [Option<Duration>; 2],
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Budgets {
+    /// Budget for all days together.
+    total: Option<Duration>,
+    /// Default budget for both parts of a day together.
+    day: Option<Duration>,
+    /// Default budget for every part.
+    part: Option<Duration>,
+    days: HashMap<Day, DayBudgets>,
+}
+
+/// A day or part that took longer than its budget.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// What exceeded its budget, e.g. `Day 04 Part 1` or `Total`.
+    pub scope: String,
+    pub duration: Duration,
+    pub budget: Duration,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} took {:.1?}, budget {:?}",
+            self.scope, self.duration, self.budget
+        )
+    }
+}
+
+impl Budgets {
+    /// Reads the budgets from [`BUDGETS_PATH`].
+    pub fn load() -> Result<Self, String> {
+        let s = fs::read_to_string(BUDGETS_PATH)
+            .map_err(|e| format!("Failed to read \"{BUDGETS_PATH}\": {e}"))?;
+        Self::parse(&s).map_err(|e| format!("Invalid \"{BUDGETS_PATH}\": {e}"))
+    }
+
+    /// Parses budgets with one budget per line, e.g. `total 10s`, `day 1s`, `part 100ms`, `day 05 2s` or `day 05 part 2 500ms`.
+    /// Durations accept the units of [`parse_duration`].
+    /// Empty lines and lines starting with `#` are ignored.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let mut budgets = Budgets::default();
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = |e: String| format!("line {}: {e}", i + 1);
+            let words = line.split_whitespace().collect::<Vec<_>>();
+
+            match words.as_slice() {
+                ["total", budget] => budgets.total = Some(parse_duration(budget).map_err(error)?),
+                ["day", budget] => budgets.day = Some(parse_duration(budget).map_err(error)?),
+                ["part", budget] => budgets.part = Some(parse_duration(budget).map_err(error)?),
+                ["day", day, budget] => {
+                    let day = parse_day(day).map_err(error)?;
+                    budgets.days.entry(day).or_default().total =
+                        Some(parse_duration(budget).map_err(error)?);
+                }
+                ["day", day, "part", part @ ("1" | "2"), budget] => {
+                    let day = parse_day(day).map_err(error)?;
+                    let part = if *part == "1" { 0 } else { 1 };
+                    budgets.days.entry(day).or_default().parts[part] =
+                        Some(parse_duration(budget).map_err(error)?);
+                }
+                _ => {
+                    return Err(error(format!(
+                        "expecting a budget like `day 05 part 2 500ms`, found `{line}`"
+                    )))
+                }
+            }
+        }
+
+        Ok(budgets)
+    }
+
+    /// Returns the days and parts whose timings exceed their budgets, followed by the total if it exceeds its budget.
+    pub fn check(&self, timings: &[Timings]) -> Vec<Violation> {
+        let mut violations = vec![];
+
+        let mut check = |scope: String, duration: Duration, budget: Option<Duration>| {
+            if let Some(budget) = budget.filter(|budget| duration > *budget) {
+                violations.push(Violation {
+                    scope,
+                    duration,
+                    budget,
+                });
+            }
+        };
+
+        for timing in timings {
+            let day = self.days.get(&timing.day);
+
+            for (i, part) in [&timing.part_1, &timing.part_2].into_iter().enumerate() {
+                let Some(duration) = part.as_deref().and_then(|d| parse_duration(d).ok()) else {
+                    continue;
+                };
+
+                let budget = day.and_then(|day| day.parts[i]).or(self.part);
+                check(
+                    format!("Day {} Part {}", timing.day, i + 1),
+                    duration,
+                    budget,
+                );
+            }
+
+            let budget = day.and_then(|day| day.total).or(self.day);
+            check(
+                format!("Day {}", timing.day),
+                from_nanos(timing.total_nanos),
+                budget,
+            );
+        }
+
+        let total = timings.iter().map(|timing| timing.total_nanos).sum::<f64>();
+        check("Total".into(), from_nanos(total), self.total);
+
+        violations
+    }
+}
+
+fn parse_day(s: &str) -> Result<Day, String> {
+    s.parse()
+        .map_err(|_| format!("expecting a day between 1 and 25, found `{s}`"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Budgets, Violation};
+    use crate::template::readme_benchmarks::Timings;
+    use crate::Day;
+    use std::time::Duration;
+
+    fn timings(day: u8, part_1: &str, part_2: &str, total_nanos: f64) -> Timings {
+        Timings {
+            day: Day::new(day).unwrap(),
+            part_1: Some(part_1.into()),
+            part_2: Some(part_2.into()),
+            part_1_allocs: None,
+            part_2_allocs: None,
+            total_nanos,
+        }
+    }
+
+    #[test]
+    fn parses_budgets() {
+        let budgets = Budgets::parse(
+            "# comment\ntotal 1s\n\nday 500ms\npart 100ms\nday 05 2s\nday 5 part 2 1s\n",
+        )
+        .unwrap();
+        assert_eq!(budgets.total, Some(Duration::from_secs(1)));
+        assert_eq!(budgets.days.len(), 1);
+
+        assert!(Budgets::parse("day 26 1s").is_err());
+        assert!(Budgets::parse("day 05 part 3 1s").is_err());
+        assert!(Budgets::parse("week 1s")
+            .unwrap_err()
+            .starts_with("line 1:"));
+    }
+
+    #[test]
+    fn finds_violations() {
+        let budgets =
+            Budgets::parse("total 1s\nday 500ms\npart 100ms\nday 05 part 2 1s\nday 05 2s").unwrap();
+
+        let violations = budgets.check(&[
+            timings(1, "50.0ms", "150.0ms", 200_000_000.0),
+            timings(5, "10.0ms", "900.0ms", 910_000_000.0),
+        ]);
+
+        assert_eq!(
+            violations,
+            vec![
+                Violation {
+                    scope: "Day 01 Part 2".into(),
+                    duration: Duration::from_millis(150),
+                    budget: Duration::from_millis(100),
+                },
+                Violation {
+                    scope: "Total".into(),
+                    duration: Duration::from_millis(1110),
+                    budget: Duration::from_secs(1),
+                },
+            ]
+        );
+        assert_eq!(
+            violations[0].to_string(),
+            "Day 01 Part 2 took 150.0ms, budget 100ms"
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
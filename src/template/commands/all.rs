@@ -1,51 +1,302 @@
-use std::io;
+use std::{io, process};
 
 use crate::template::{
+    budgets::Budgets,
+    limits::{LimitExceeded, Limits},
+    machine::{self, Metadata},
+    output,
+    profiles::Profile,
     readme_benchmarks::{self, Timings},
-    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
 use crate::{all_days, Day};
 
-pub fn handle(is_release: bool, is_timed: bool) {
+/// Flags passed to `all`.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub release: bool,
+    pub time: bool,
+    pub track_allocs: bool,
+    pub limits: Limits,
+    pub pin_cpu: Option<usize>,
+    /// Profiles to run all solutions under. Defaults to the profile selected by `--release`.
+    pub profiles: Vec<Profile>,
+    /// Exit with a non-zero status if any timing exceeds its budget in `budgets.txt`.
+    pub enforce_budgets: bool,
+}
+
+pub fn handle(options: &Options) {
+    // fail early on invalid budgets, rather than after running all solutions.
+    let budgets = if options.enforce_budgets {
+        match Budgets::load() {
+            Ok(budgets) => Some(budgets),
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        }
+    } else {
+        None
+    };
+
+    let profiles = if options.profiles.is_empty() {
+        vec![Profile::from_release_flag(options.release)]
+    } else {
+        options.profiles.clone()
+    };
+
+    if options.time {
+        machine::warn_about_governor(options.pin_cpu);
+    }
+
+    let mut results: Vec<(&Profile, Vec<Timings>)> = vec![];
+    let mut failures: Vec<String> = vec![];
+
+    for profile in &profiles {
+        if profiles.len() > 1 {
+            output::status(output::bold(format!("Profile {profile}")));
+            output::status("==========");
+        }
+
+        let (timings, profile_failures) = run_days(options, profile);
+
+        if profiles.len() > 1 {
+            output::status("");
+            failures.extend(
+                profile_failures
+                    .into_iter()
+                    .map(|f| format!("{f} [{profile}]")),
+            );
+        } else {
+            failures.extend(profile_failures);
+        }
+
+        results.push((profile, timings));
+    }
+
+    if !failures.is_empty() {
+        eprintln!("\n{} {}", output::bold("Failed:"), failures.join(", "));
+    }
+
+    if options.time {
+        let names = profiles.iter().map(Profile::name).collect::<Vec<_>>();
+        let metadata = Metadata::collect(&names.join(", "), options.pin_cpu);
+
+        if let [(profile, timings)] = results.as_slice() {
+            let total_millis = total_millis(timings);
+
+            println!(
+                "\n{} {}",
+                output::bold("Total:"),
+                output::italic(format!("{total_millis:.2}ms"))
+            );
+            println!("{} {metadata}", output::bold("Environment:"));
+
+            if profile.is_optimized() && failures.is_empty() {
+                match readme_benchmarks::update(timings.clone(), total_millis, &metadata) {
+                    Ok(()) => output::status("Successfully updated README with benchmarks."),
+                    Err(_) => {
+                        eprintln!("Failed to update readme with benchmarks.");
+                    }
+                }
+            }
+        } else {
+            println!("\n{}", comparison_table(&results));
+            println!("\n{} {metadata}", output::bold("Environment:"));
+        }
+    }
+
+    let mut violations = vec![];
+
+    if let Some(budgets) = &budgets {
+        for (profile, timings) in &results {
+            violations.extend(budgets.check(timings).into_iter().map(|violation| {
+                if profiles.len() > 1 {
+                    format!("{violation} [{profile}]")
+                } else {
+                    violation.to_string()
+                }
+            }));
+        }
+
+        if violations.is_empty() {
+            output::status("All solutions are within their budgets.");
+        } else {
+            eprintln!("\n{}", output::bold("Over budget:"));
+            for violation in &violations {
+                eprintln!("  {violation}");
+            }
+        }
+    }
+
+    if !failures.is_empty() || !violations.is_empty() {
+        process::exit(1);
+    }
+}
+
+/// Runs all scaffolded solutions under a profile, returning their timings and the parts that failed.
+fn run_days(options: &Options, profile: &Profile) -> (Vec<Timings>, Vec<String>) {
     let mut timings: Vec<Timings> = vec![];
+    let mut failures: Vec<String> = vec![];
+
+    output::status(output::italic(format!("Building solutions ({profile})...")));
+
+    let build = match child_commands::build_solutions(options, profile) {
+        Ok(build) => build,
+        Err(Error::Build(stderr)) => {
+            eprintln!("{stderr}");
+            eprintln!(
+                "\n{} the library does not compile.",
+                output::bold("Failed:")
+            );
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Failed to build solutions: {e:?}");
+            process::exit(1);
+        }
+    };
 
     all_days().for_each(|day| {
         if day > 1 {
-            println!();
+            output::status("");
         }
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+        output::status(output::bold(format!("Day {day}")));
+        output::status("------");
+
+        if let Some(errors) = build.errors.get(&day) {
+            println!("{}⚠ failed to compile", quiet_prefix(day));
+            eprintln!("{}", errors.trim_end());
+            failures.push(format!("Day {day} (failed to compile)"));
+            return;
+        }
+
+        // days that have not been scaffolded yet have no bin.
+        let Some(executable) = build.executables.get(&day) else {
+            output::status("Not solved.");
+            return;
+        };
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let (output, exceeded) = child_commands::run_solution(day, executable, options).unwrap();
+
+        if let Some(exceeded) = &exceeded {
+            println!("{}⚠ {exceeded}", quiet_prefix(day));
+            failures.push(match exceeded {
+                LimitExceeded::Stack(Some(part)) => format!("Day {day} {part} (stack overflow)"),
+                _ => format!("Day {day} ({exceeded})"),
+            });
+        }
 
         if output.is_empty() {
-            println!("Not solved.");
+            if exceeded.is_none() {
+                output::status("Not solved.");
+            }
         } else {
             let val = child_commands::parse_exec_time(&output, day);
             timings.push(val);
+
+            failures.extend(
+                child_commands::parse_failed_parts(&output)
+                    .into_iter()
+                    .map(|part| format!("Day {day} {part}")),
+            );
         }
     });
 
-    if is_timed {
-        let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
+    (timings, failures)
+}
 
-        println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+/// `--quiet` omits the day headers, so lines are prefixed with their day instead.
+fn quiet_prefix(day: Day) -> String {
+    if output::is_quiet() {
+        format!("Day {day} ")
+    } else {
+        String::new()
+    }
+}
 
-        if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
-                Err(_) => {
-                    eprintln!("Failed to update readme with benchmarks.");
-                }
+fn total_millis(timings: &[Timings]) -> f64 {
+    timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
+}
+
+/// Formats the timings of several profiles side by side, one row per solved part.
+fn comparison_table(results: &[(&Profile, Vec<Timings>)]) -> String {
+    let mut days = results
+        .iter()
+        .flat_map(|(_, timings)| timings.iter().map(|t| t.day))
+        .collect::<Vec<Day>>();
+    days.sort_unstable();
+    days.dedup();
+
+    let mut rows: Vec<Vec<String>> = vec![];
+
+    let mut header = vec!["Day".to_string(), "Part".to_string()];
+    header.extend(results.iter().map(|(profile, _)| profile.to_string()));
+    rows.push(header);
+
+    for day in days {
+        for part in [1, 2] {
+            let cells = results
+                .iter()
+                .map(|(_, timings)| {
+                    timings
+                        .iter()
+                        .find(|t| t.day == day)
+                        .and_then(|t| if part == 1 { &t.part_1 } else { &t.part_2 }.clone())
+                })
+                .collect::<Vec<_>>();
+
+            if cells.iter().all(Option::is_none) {
+                continue;
             }
+
+            let mut row = vec![day.to_string(), part.to_string()];
+            row.extend(cells.into_iter().map(|c| c.unwrap_or_else(|| "-".into())));
+            rows.push(row);
         }
     }
+
+    // totals only include days that succeeded in every profile, so that they compare the same work.
+    // they are relative to the first profile, e.g. `0.20ms (0.85x)`.
+    let common = |timings: &Vec<Timings>| {
+        timings
+            .iter()
+            .filter(|t| is_in_every_profile(t, results))
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+
+    let baseline = total_millis(&common(&results[0].1));
+    let mut totals = vec!["Total".to_string(), String::new()];
+    totals.extend(results.iter().map(|(_, timings)| {
+        let total = total_millis(&common(timings));
+        if baseline > 0.0 {
+            format!("{total:.2}ms ({:.2}x)", total / baseline)
+        } else {
+            format!("{total:.2}ms")
+        }
+    }));
+    rows.push(totals);
+
+    output::table(&rows)
+}
+
+/// Whether every profile timed the same parts of the day of `timing`, i.e. none of them failed or skipped a part.
+fn is_in_every_profile(timing: &Timings, results: &[(&Profile, Vec<Timings>)]) -> bool {
+    let parts = |t: &Timings| (t.part_1.is_some(), t.part_2.is_some());
+
+    results.iter().all(|(_, timings)| {
+        timings
+            .iter()
+            .any(|t| t.day == timing.day && parts(t) == parts(timing))
+    })
 }
 
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
+    /// The library failed to compile, so no solution could be built. Holds the output of `cargo build`.
+    Build(String),
     Parser(String),
     IO(io::Error),
 }
@@ -63,40 +314,57 @@ pub fn get_path_for_bin(day: Day) -> String {
 
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
-    use super::{get_path_for_bin, Error};
+pub(crate) mod child_commands {
+    use super::{quiet_prefix, Error, Options};
+    use crate::template::duration::parse_nanos;
+    use crate::template::json::Value;
+    use crate::template::limits::LimitExceeded;
+    use crate::template::output;
+    use crate::template::profiles::Profile;
     use crate::Day;
     use std::{
+        collections::HashMap,
         io::{BufRead, BufReader},
-        path::Path,
+        path::{Path, PathBuf},
         process::{Command, Stdio},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
-
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    /// Run the solution bin for a given day, built by [`build_solutions`].
+    /// Returns the lines printed to stdout and the resource limit the solution exceeded, if any.
+    pub fn run_solution(
+        day: Day,
+        executable: &Path,
+        options: &Options,
+    ) -> Result<(Vec<String>, Option<LimitExceeded>), Error> {
+        let mut args = vec![];
 
-        if is_release {
-            args.push("--release");
+        if options.time {
+            // mirror `--time` flag to child invocations.
+            args.push("--time".to_string());
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        if let Some(cpu) = options.pin_cpu {
+            args.push("--pin-cpu".to_string());
+            args.push(cpu.to_string());
         }
 
+        args.extend(options.limits.to_args());
+        args.extend(output::verbosity().to_args());
+
+        output::detail(format!(
+            "Running `{} {}`",
+            executable.display(),
+            args.join(" ")
+        ));
+
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
-        let mut cmd = Command::new("cargo")
+        let mut cmd = Command::new(executable);
+        output::forward_terminal(&mut cmd);
+
+        let mut cmd = cmd
             .args(&args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -105,24 +373,132 @@ mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let mut lines = vec![];
 
         let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+            stderr
+                .lines()
+                .map(|line| {
+                    let line = line.unwrap();
+                    eprintln!("{line}");
+                    line
+                })
+                .collect::<Vec<String>>()
         });
 
+        let prefix = quiet_prefix(day);
+
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            println!("{prefix}{line}");
+            lines.push(line);
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        let stderr = thread.join().unwrap();
+        let status = cmd.wait()?;
+
+        Ok((lines, options.limits.exceeded(&status, &stderr)))
+    }
 
-        Ok(output)
+    /// The solution bins of all days, built under one profile.
+    #[derive(Debug, Default, PartialEq, Eq)]
+    pub struct Build {
+        /// Paths of the bins that compiled, as reported by cargo.
+        pub executables: HashMap<Day, PathBuf>,
+        /// Rendered errors of the bins that failed to compile.
+        pub errors: HashMap<Day, String>,
+    }
+
+    /// Builds the solution bins of all days under a profile with a single `cargo build`, so that they can be run directly.
+    /// Returns an error if the library itself failed to compile, as then no bin could be built.
+    pub fn build_solutions(options: &Options, profile: &Profile) -> Result<Build, Error> {
+        // unlike `json-render-diagnostics`, this keeps diagnostics in the messages, where they are tagged with their bin.
+        let message_format = if output::use_color() {
+            "--message-format=json-diagnostic-rendered-ansi"
+        } else {
+            "--message-format=json"
+        };
+
+        let mut cmd = Command::new("cargo");
+        cmd.args(["build", "--quiet", "--bins", "--keep-going", message_format])
+            .args(profile.cargo_args());
+
+        if options.track_allocs {
+            cmd.args(["--features", "track_allocs"]);
+        }
+
+        profile.set_rustflags(&mut cmd);
+        output::detail(format!(
+            "Running `cargo build --quiet --bins --keep-going {message_format} {}`",
+            profile.cargo_args().join(" ")
+        ));
+
+        let result = cmd.output()?;
+        let (build, library_errors) =
+            parse_build_messages(&String::from_utf8_lossy(&result.stdout));
+
+        if result.status.success() || !build.errors.is_empty() {
+            return Ok(build);
+        }
+
+        // e.g. the library does not compile, or cargo failed before compiling anything.
+        Err(Error::Build(if library_errors.is_empty() {
+            String::from_utf8_lossy(&result.stderr)
+                .trim_end()
+                .to_string()
+        } else {
+            library_errors.trim_end().to_string()
+        }))
+    }
+
+    /// Collects the bins and errors from the JSON messages printed by `cargo build --message-format=json`, one per line.
+    /// Errors of targets other than bins, i.e. the library, are returned separately.
+    fn parse_build_messages(stdout: &str) -> (Build, String) {
+        let mut build = Build::default();
+        let mut library_errors = String::new();
+
+        for message in stdout.lines().filter_map(|line| Value::parse(line).ok()) {
+            let target = message.get("target");
+            let is_bin = target
+                .and_then(|target| target.get("kind"))
+                .and_then(Value::as_array)
+                .is_some_and(|kinds| kinds.iter().any(|kind| kind.as_str() == Some("bin")));
+            let day = target
+                .and_then(|target| target.get("name"))
+                .and_then(Value::as_str)
+                .and_then(|name| name.parse::<Day>().ok())
+                .filter(|_| is_bin);
+
+            match message.get("reason").and_then(Value::as_str) {
+                Some("compiler-artifact") => {
+                    let executable = message.get("executable").and_then(Value::as_str);
+                    if let (Some(day), Some(executable)) = (day, executable) {
+                        build.executables.insert(day, PathBuf::from(executable));
+                    }
+                }
+                Some("compiler-message") => {
+                    let Some(diagnostic) = message.get("message") else {
+                        continue;
+                    };
+
+                    let is_error = diagnostic
+                        .get("level")
+                        .and_then(Value::as_str)
+                        .is_some_and(|level| level.starts_with("error"));
+                    let rendered = diagnostic.get("rendered").and_then(Value::as_str);
+
+                    if let (true, Some(rendered)) = (is_error, rendered) {
+                        match day {
+                            Some(day) => build.errors.entry(day).or_default().push_str(rendered),
+                            None => library_errors.push_str(rendered),
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        (build, library_errors)
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
@@ -130,6 +506,8 @@ mod child_commands {
             day,
             part_1: None,
             part_2: None,
+            part_1_allocs: None,
+            part_2_allocs: None,
             total_nanos: 0_f64,
         };
 
@@ -146,13 +524,15 @@ mod child_commands {
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((part, timing_str, nanos, parse_allocs(l)))
             })
-            .for_each(|(part, timing_str, nanos)| {
+            .for_each(|(part, timing_str, nanos, allocs)| {
                 if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
+                    timings.part_1_allocs = allocs.map(str::to_string);
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
+                    timings.part_2_allocs = allocs.map(str::to_string);
                 }
 
                 timings.total_nanos += nanos;
@@ -161,27 +541,37 @@ mod child_commands {
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    /// Returns the names of all parts that reported an error, e.g. `Part 1`.
+    pub fn parse_failed_parts(output: &[String]) -> Vec<&str> {
+        output
+            .iter()
+            .filter(|l| l.contains(": ⚠ failed"))
+            .filter_map(|l| l.split(':').next())
+            .map(str::trim)
+            .collect()
+    }
+
+    /// Extracts the allocation statistics that follow the timing, e.g. `3 allocs, 1.0 KiB total, 512 B peak`.
+    fn parse_allocs(line: &str) -> Option<&str> {
+        line.split(" samples)")
+            .nth(1)?
+            .trim()
+            .strip_prefix('[')?
+            .strip_suffix(']')
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
+    pub fn parse_time(line: &str) -> Option<(&str, f64)> {
         // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        let parsed_timing = parse_nanos(str_timing).ok()?;
 
         Some((str_timing, parsed_timing))
     }
@@ -202,7 +592,8 @@ mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_build_messages, parse_exec_time, parse_failed_parts, Build};
+        use std::{collections::HashMap, path::PathBuf};
 
         use crate::day;
 
@@ -219,6 +610,27 @@ mod child_commands {
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
             assert_eq!(res.part_1.unwrap(), "74.13ns");
             assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.part_1_allocs.is_none(), true);
+        }
+
+        #[test]
+        fn test_allocations() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples) [3 allocs, 1.0 KiB total, 512 B peak]"
+                        .into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
+            assert_eq!(res.part_1.unwrap(), "74.13ns");
+            assert_eq!(
+                res.part_1_allocs.unwrap(),
+                "3 allocs, 1.0 KiB total, 512 B peak"
+            );
+            assert_eq!(res.part_2_allocs.is_none(), true);
         }
 
         #[test]
@@ -250,5 +662,108 @@ mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn test_failed_parts() {
+            let output = [
+                "Part 1: ⚠ failed (1.0µs)".into(),
+                "day 01: invalid number `x` at line 1, column 1".into(),
+                "Part 2: ✖        ".into(),
+                "".into(),
+            ];
+            let res = parse_exec_time(&output, day!(1));
+            assert_approx_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.part_1.is_none(), true);
+            assert_eq!(parse_failed_parts(&output), vec!["Part 1"]);
+        }
+
+        #[test]
+        fn test_build_messages() {
+            let stdout = [
+                r#"{"reason":"compiler-artifact","target":{"kind":["lib"],"name":"advent_of_code"},"executable":null,"fresh":true}"#,
+                r#"{"reason":"compiler-artifact","target":{"kind":["bin"],"name":"01"},"executable":"/target/release/01","fresh":true}"#,
+                r#"{"reason":"compiler-message","target":{"kind":["bin"],"name":"05"},"message":{"level":"warning","rendered":"warning: unused variable\n"}}"#,
+                r#"{"reason":"compiler-message","target":{"kind":["bin"],"name":"05"},"message":{"level":"error","rendered":"error[E0308]: mismatched types\n --> src/bin/05.rs:3:5\n"}}"#,
+                r#"{"reason":"compiler-message","target":{"kind":["bin"],"name":"05"},"message":{"level":"error","rendered":"error: aborting due to 1 previous error\n"}}"#,
+                r#"{"reason":"build-finished","success":false}"#,
+            ]
+            .join("\n");
+
+            let (build, library_errors) = parse_build_messages(&stdout);
+            assert_eq!(
+                build.executables,
+                HashMap::from([(day!(1), PathBuf::from("/target/release/01"))])
+            );
+            assert_eq!(
+                build.errors,
+                HashMap::from([(
+                    day!(5),
+                    "error[E0308]: mismatched types\n --> src/bin/05.rs:3:5\nerror: aborting due to 1 previous error\n".to_string()
+                )])
+            );
+            assert!(library_errors.is_empty());
+
+            let (build, library_errors) = parse_build_messages(
+                r#"{"reason":"compiler-message","target":{"kind":["lib"],"name":"advent_of_code"},"message":{"level":"error","rendered":"error: expected `;`\n"}}"#,
+            );
+            assert_eq!(build, Build::default());
+            assert_eq!(library_errors, "error: expected `;`\n");
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::comparison_table;
+    use crate::template::profiles::Profile;
+    use crate::template::readme_benchmarks::Timings;
+    use crate::Day;
+
+    fn timings(day: u8, part_1: &str, part_2: Option<&str>, total_nanos: f64) -> Timings {
+        Timings {
+            day: Day::new(day).unwrap(),
+            part_1: Some(part_1.into()),
+            part_2: part_2.map(str::to_string),
+            part_1_allocs: None,
+            part_2_allocs: None,
+            total_nanos,
+        }
+    }
+
+    #[test]
+    fn compares_profiles() {
+        let (release, native) = (Profile::new("release"), Profile::new("native"));
+        let results = [
+            (
+                &release,
+                vec![
+                    timings(1, "20.0µs", Some("30.0µs"), 50_000.0),
+                    timings(2, "1.0ms", None, 1_000_000.0),
+                    timings(3, "6.0µs", Some("40.0µs"), 46_000.0),
+                ],
+            ),
+            (
+                &native,
+                vec![
+                    timings(1, "10.0µs", Some("15.0µs"), 25_000.0),
+                    // e.g. part 2 failed.
+                    timings(3, "5.0µs", None, 5_000.0),
+                ],
+            ),
+        ];
+
+        // only day 1 succeeded in both profiles, so the totals only include day 1.
+        let expected = [
+            "Day    Part  release         native",
+            "01     1     20.0µs          10.0µs",
+            "01     2     30.0µs          15.0µs",
+            "02     1     1.0ms           -",
+            "03     1     6.0µs           5.0µs",
+            "03     2     40.0µs          -",
+            "Total        0.05ms (1.00x)  0.03ms (0.50x)",
+        ]
+        .join("\n");
+
+        assert_eq!(comparison_table(&results), expected);
     }
 }
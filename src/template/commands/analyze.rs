@@ -0,0 +1,76 @@
+/// Runs a solution on generated inputs of growing size to estimate how it scales.
+use std::process::{self, Command};
+
+use crate::template::output;
+use crate::Day;
+
+/// Flags passed to `analyze`.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub release: bool,
+    pub part: Option<u8>,
+    /// Analyze this variant instead of the first one.
+    pub variant: Option<String>,
+    pub all_variants: bool,
+    /// Comma-separated sizes to generate inputs for, instead of doubling sizes.
+    pub sizes: Option<String>,
+    /// Directory to write SVG plots to.
+    pub svg: Option<String>,
+}
+
+pub fn handle(day: Day, options: &Options) {
+    if !options.release {
+        eprintln!("Warning: analyzing without `--release`, timings of debug builds may scale differently.");
+    }
+
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+
+    if output::is_quiet() {
+        cmd_args.push("--quiet".to_string());
+    }
+
+    if options.release {
+        cmd_args.push("--release".to_string());
+    }
+
+    cmd_args.push("--".to_string());
+    cmd_args.push("--analyze".to_string());
+
+    if let Some(part) = options.part {
+        cmd_args.push("--part".to_string());
+        cmd_args.push(part.to_string());
+    }
+
+    if let Some(variant) = &options.variant {
+        cmd_args.push("--variant".to_string());
+        cmd_args.push(variant.clone());
+    }
+
+    if options.all_variants {
+        cmd_args.push("--all-variants".to_string());
+    }
+
+    if let Some(sizes) = &options.sizes {
+        cmd_args.push("--sizes".to_string());
+        cmd_args.push(sizes.clone());
+    }
+
+    if let Some(svg) = &options.svg {
+        cmd_args.push("--svg".to_string());
+        cmd_args.push(svg.clone());
+    }
+
+    cmd_args.extend(output::verbosity().to_args());
+
+    output::detail(format!("Running `cargo {}`", cmd_args.join(" ")));
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(&cmd_args);
+    output::forward_terminal(&mut cmd);
+
+    let status = cmd.status().unwrap();
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
@@ -0,0 +1,427 @@
+/// Compares the timings and answers of solutions between two git revisions, e.g. before and after an optimisation.
+/// Both revisions are checked out into temporary worktrees, built in release and run on the inputs of the current tree.
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::{env, fs};
+
+use super::all::child_commands::parse_time;
+use crate::template::output;
+use crate::{all_days, Day};
+
+/// Flags passed to `compare`.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub rev_a: String,
+    pub rev_b: String,
+    /// The days to compare. Defaults to all days.
+    pub days: Vec<Day>,
+}
+
+pub fn handle(options: &Options) {
+    match compare(options) {
+        Ok(true) => {}
+        Ok(false) => {
+            eprintln!(
+                "\n{} the answers of {} and {} differ.",
+                output::bold("Failed:"),
+                options.rev_a,
+                options.rev_b
+            );
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Runs the comparison and returns whether all answers are identical.
+/// Worktrees are removed when this returns, so it must not exit the process.
+fn compare(options: &Options) -> Result<bool, String> {
+    let worktrees = [
+        Worktree::add(&options.rev_a, "a")?,
+        Worktree::add(&options.rev_b, "b")?,
+    ];
+
+    for worktree in &worktrees {
+        output::status(format!(
+            "Building {} ({}) in release...",
+            worktree.rev, worktree.hash
+        ));
+        worktree.build()?;
+        worktree.copy_inputs()?;
+    }
+
+    let days = if options.days.is_empty() {
+        all_days().collect::<Vec<_>>()
+    } else {
+        options.days.clone()
+    };
+
+    let mut rows = vec![vec![
+        "Day".to_string(),
+        "Part".to_string(),
+        worktrees[0].rev.clone(),
+        worktrees[1].rev.clone(),
+        "Speedup".to_string(),
+        "Answers".to_string(),
+    ]];
+    let mut identical = true;
+
+    for day in days {
+        if !worktrees.iter().any(|worktree| worktree.has_day(day)) {
+            continue;
+        }
+
+        output::status(format!("Running day {day}..."));
+
+        // the revisions run right after each other, so that both see similar machine conditions.
+        let [runs_a, runs_b] = [&worktrees[0], &worktrees[1]].map(|worktree| worktree.run(day));
+
+        let mut parts = runs_a.iter().map(|run| &run.part).collect::<Vec<_>>();
+        for run in &runs_b {
+            if !parts.contains(&&run.part) {
+                parts.push(&run.part);
+            }
+        }
+
+        for part in parts {
+            let a = runs_a.iter().find(|run| &run.part == part);
+            let b = runs_b.iter().find(|run| &run.part == part);
+            let time = |run: Option<&PartRun>| {
+                run.and_then(|run| run.time.as_ref())
+                    .map_or_else(|| "-".to_string(), |(time, _)| time.clone())
+            };
+
+            let speedup = match (
+                a.and_then(|a| a.time.as_ref()),
+                b.and_then(|b| b.time.as_ref()),
+            ) {
+                (Some((_, a)), Some((_, b))) if *b > 0.0 => format!("{:.2}×", a / b),
+                _ => "-".to_string(),
+            };
+
+            let answers = match (a, b) {
+                (Some(a), Some(b)) if a.answer == b.answer => "✓".to_string(),
+                // e.g. a part that was implemented in between the revisions.
+                (Some(run), Some(_)) | (Some(_), Some(run)) if run.answer == UNSOLVED => {
+                    let rev = if a == Some(run) {
+                        &options.rev_a
+                    } else {
+                        &options.rev_b
+                    };
+                    format!("unsolved in {rev}")
+                }
+                (Some(a), Some(b)) => {
+                    identical = false;
+                    format!(
+                        "✗ {} ≠ {}",
+                        a.answer.replace('\n', "⏎"),
+                        b.answer.replace('\n', "⏎")
+                    )
+                }
+                _ => "-".to_string(),
+            };
+
+            rows.push(vec![
+                format!("Day {day}"),
+                part.clone(),
+                time(a),
+                time(b),
+                speedup,
+                answers,
+            ]);
+        }
+    }
+
+    println!("\n{}", output::table(&rows));
+    Ok(identical)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A revision checked out into a temporary directory. The worktree is removed when dropped.
+struct Worktree {
+    rev: String,
+    hash: String,
+    path: PathBuf,
+    /// Build artifacts are kept per commit below `target/compare`, so that comparing a commit again is fast.
+    target_dir: PathBuf,
+}
+
+impl Worktree {
+    fn add(rev: &str, suffix: &str) -> Result<Self, String> {
+        let hash = git(&[
+            "rev-parse",
+            "--short=12",
+            "--verify",
+            &format!("{rev}^{{commit}}"),
+        ])
+        .map_err(|e| format!("Unknown revision `{rev}`: {e}"))?;
+
+        let path = env::temp_dir().join(format!("aoc-compare-{hash}-{suffix}"));
+
+        // left behind by an interrupted comparison.
+        if path.exists() {
+            let _ = git(&["worktree", "remove", "--force", &path.to_string_lossy()]);
+            let _ = fs::remove_dir_all(&path);
+            let _ = git(&["worktree", "prune"]);
+        }
+
+        git(&[
+            "worktree",
+            "add",
+            "--detach",
+            "--quiet",
+            &path.to_string_lossy(),
+            &hash,
+        ])
+        .map_err(|e| format!("Failed to check out `{rev}`: {e}"))?;
+
+        let target_dir = env::var_os("CARGO_TARGET_DIR")
+            .map_or_else(|| "target".into(), PathBuf::from)
+            .join("compare")
+            .join(&hash);
+
+        let target_dir = env::current_dir()
+            .map_err(|e| e.to_string())?
+            .join(target_dir);
+
+        Ok(Self {
+            rev: rev.to_string(),
+            hash,
+            path,
+            target_dir,
+        })
+    }
+
+    fn build(&self) -> Result<(), String> {
+        let status = Command::new("cargo")
+            .args(["build", "--quiet", "--release", "--bins"])
+            .current_dir(&self.path)
+            .env("CARGO_TARGET_DIR", &self.target_dir)
+            .status()
+            .map_err(|e| e.to_string())?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Failed to build `{}`: cargo exited with {status}",
+                self.rev
+            ))
+        }
+    }
+
+    /// Puzzle inputs are not committed, so both revisions run on the inputs of the current tree.
+    fn copy_inputs(&self) -> Result<(), String> {
+        let inputs = Path::new("data").join("inputs");
+        let target = self.path.join(&inputs);
+        fs::create_dir_all(&target).map_err(|e| e.to_string())?;
+
+        let Ok(entries) = fs::read_dir(&inputs) else {
+            return Ok(());
+        };
+
+        for entry in entries.filter_map(Result::ok) {
+            fs::copy(entry.path(), target.join(entry.file_name())).map_err(|e| e.to_string())?;
+        }
+
+        Ok(())
+    }
+
+    fn has_day(&self, day: Day) -> bool {
+        self.path.join(super::all::get_path_for_bin(day)).exists()
+    }
+
+    /// Runs the timed solution of a day. Days that do not exist in this revision have no parts.
+    fn run(&self, day: Day) -> Vec<PartRun> {
+        if !self.has_day(day) {
+            return vec![];
+        }
+
+        let binary = self
+            .target_dir
+            .join("release")
+            .join(format!("{day}{}", env::consts::EXE_SUFFIX));
+
+        output::detail(format!("Running `{} --time`", binary.display()));
+
+        let result = Command::new(&binary)
+            .arg("--time")
+            .current_dir(&self.path)
+            .stderr(Stdio::inherit())
+            .output();
+
+        match result {
+            Ok(result) => parse_parts(&String::from_utf8_lossy(&result.stdout)),
+            Err(e) => {
+                eprintln!("Failed to run day {day} of `{}`: {e}", self.rev);
+                vec![]
+            }
+        }
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        if let Err(e) = git(&[
+            "worktree",
+            "remove",
+            "--force",
+            &self.path.to_string_lossy(),
+        ]) {
+            eprintln!("Failed to remove worktree \"{}\": {e}", self.path.display());
+        }
+    }
+}
+
+fn git(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Printed instead of an answer by parts that return [`None`].
+const UNSOLVED: &str = "✖";
+
+/// The answer and timing of one part, as printed by a solution binary.
+#[derive(Debug, PartialEq)]
+struct PartRun {
+    part: String,
+    answer: String,
+    /// The printed duration and the duration in nanoseconds.
+    time: Option<(String, f64)>,
+}
+
+/// Parses the output of a solution binary. Older revisions may print colors and in-place updates, which are ignored.
+fn parse_parts(stdout: &str) -> Vec<PartRun> {
+    let mut runs: Vec<PartRun> = vec![];
+    let mut is_multiline = false;
+
+    for line in stdout.lines() {
+        let line = strip_ansi(line.rsplit('\r').next().unwrap_or(line));
+
+        let Some((part, rest)) = line
+            .split_once(": ")
+            .filter(|(part, _)| part.starts_with("Part "))
+        else {
+            // lines of a multi-line answer, or of an error.
+            if let Some(run) = runs.last_mut().filter(|_| is_multiline) {
+                run.answer.push('\n');
+                run.answer.push_str(&line);
+            }
+            continue;
+        };
+
+        let time = parse_time(&line)
+            .filter(|_| line.contains(" samples)"))
+            .map(|(time, nanos)| (time.to_string(), nanos));
+
+        let answer = strip_stats(rest).trim();
+
+        // the drawing is compared rather than the decoded letters of e.g. `EZ ▼`, which older revisions may not print.
+        is_multiline = answer.ends_with('▼');
+        runs.push(PartRun {
+            part: part.to_string(),
+            answer: if is_multiline {
+                String::new()
+            } else {
+                answer.to_string()
+            },
+            time,
+        });
+    }
+
+    for run in &mut runs {
+        run.answer = run.answer.trim_start_matches('\n').to_string();
+    }
+
+    runs
+}
+
+/// Strips the duration and allocations that follow an answer or a failure, e.g. `42 (1.5ms @ 667 samples)` or `⚠ failed (2.0µs)`.
+/// Failed parts are not benched, so their durations differ between runs and must not be compared.
+fn strip_stats(rest: &str) -> &str {
+    match rest.rfind(" (") {
+        Some(index) if rest[index + 2..].starts_with(|c: char| c.is_ascii_digit()) => {
+            &rest[..index]
+        }
+        _ => rest,
+    }
+}
+
+fn strip_ansi(line: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_parts, PartRun};
+
+    #[test]
+    fn parses_parts() {
+        let stdout = "Part 1: \x1b[1m13\x1b[0m > \x1b[3mbenching\x1b[0m\r\x1b[1mPart 1: \x1b[1m13\x1b[0m (1.5ms @ 667 samples)\nPart 2: ▼  (2.0µs @ 10000 samples)\n#..#\n####\nPart 3: ⚠ failed (2.0µs)\nPart 4: 7 (1.0ms) [3 allocs, 1.0 KiB total, 512 B peak]\nPart 5: f(x) (1.0ms)\nPart 6: \x1b[1mEZ\x1b[0m ▼ (3.0µs @ 10000 samples)\n####\n#...\n";
+
+        assert_eq!(
+            parse_parts(stdout),
+            vec![
+                PartRun {
+                    part: "Part 1".into(),
+                    answer: "13".into(),
+                    time: Some(("1.5ms".into(), 1_500_000.0)),
+                },
+                PartRun {
+                    part: "Part 2".into(),
+                    answer: "#..#\n####".into(),
+                    time: Some(("2.0µs".into(), 2_000.0)),
+                },
+                PartRun {
+                    part: "Part 3".into(),
+                    answer: "⚠ failed".into(),
+                    time: None,
+                },
+                PartRun {
+                    part: "Part 4".into(),
+                    answer: "7".into(),
+                    time: None,
+                },
+                PartRun {
+                    part: "Part 5".into(),
+                    answer: "f(x)".into(),
+                    time: None,
+                },
+                PartRun {
+                    part: "Part 6".into(),
+                    answer: "####\n#...".into(),
+                    time: Some(("3.0µs".into(), 3_000.0)),
+                },
+            ]
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,262 @@
+/// Prints statistics about the puzzle input of a day, to learn its shape before writing a solution.
+use std::cmp;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::process;
+
+use crate::template::{output, path_for_part, read_data_file};
+use crate::Day;
+
+pub fn handle(day: Day) {
+    let path = path_for_part("inputs", day, 1);
+
+    let input = match read_data_file(&path) {
+        Ok(input) if !input.trim().is_empty() => input,
+        Ok(_) => {
+            eprintln!(
+                "\"{}\" is empty, try `cargo download {day}`.",
+                path.display()
+            );
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Failed to read \"{}\": {e}", path.display());
+            process::exit(1);
+        }
+    };
+
+    output::status(output::bold(format!("Day {day} input")));
+    output::status("------------");
+    println!("{}", Stats::collect(&input));
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, PartialEq, Eq)]
+struct Stats {
+    lines: usize,
+    /// Widths in characters of the shortest and longest non-blank lines.
+    widths: (usize, usize),
+    /// Groups of lines separated by blank lines.
+    sections: usize,
+    /// Characters other than line breaks, by descending count.
+    histogram: Vec<(char, usize)>,
+    integers: Vec<i128>,
+}
+
+impl Stats {
+    fn collect(input: &str) -> Self {
+        let lines = input.lines().collect::<Vec<_>>();
+
+        let widths = lines
+            .iter()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.chars().count());
+
+        let sections = lines
+            .split(|line| line.trim().is_empty())
+            .filter(|section| !section.is_empty())
+            .count();
+
+        let mut counts: HashMap<char, usize> = HashMap::new();
+        for c in input.chars().filter(|c| *c != '\n' && *c != '\r') {
+            *counts.entry(c).or_default() += 1;
+        }
+
+        let mut histogram = counts.into_iter().collect::<Vec<_>>();
+        histogram.sort_unstable_by_key(|(c, count)| (cmp::Reverse(*count), *c));
+
+        Self {
+            lines: lines.len(),
+            widths: (widths.clone().min().unwrap_or(0), widths.max().unwrap_or(0)),
+            sections,
+            histogram,
+            integers: parse_integers(input),
+        }
+    }
+
+    fn is_grid(&self) -> bool {
+        self.sections == 1 && self.lines > 1 && self.widths.0 == self.widths.1
+    }
+
+    /// Returns the smallest and the largest product of two of the integers. Both are among the products of
+    /// the two largest, the two smallest, i.e. most negative, and the smallest and the largest integers.
+    fn pair_product_range(&self) -> Option<(i128, i128)> {
+        let mut sorted = self.integers.clone();
+        sorted.sort_unstable();
+
+        let n = sorted.len();
+        (n >= 2).then(|| {
+            let products = [
+                sorted[0].saturating_mul(sorted[1]),
+                sorted[n - 2].saturating_mul(sorted[n - 1]),
+                sorted[0].saturating_mul(sorted[n - 1]),
+            ];
+            (
+                products.into_iter().min().unwrap(),
+                products.into_iter().max().unwrap(),
+            )
+        })
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut rows = vec![];
+
+        let (min_width, max_width) = self.widths;
+        rows.push(vec![
+            "Lines".to_string(),
+            if self.is_grid() {
+                format!("{}, all {min_width} wide (grid)", self.lines)
+            } else if min_width == max_width {
+                format!("{}, all {min_width} wide", self.lines)
+            } else {
+                format!("{}, {min_width} to {max_width} wide", self.lines)
+            },
+        ]);
+
+        rows.push(vec!["Sections".to_string(), self.sections.to_string()]);
+
+        let histogram = self
+            .histogram
+            .iter()
+            .map(|(c, count)| format!("{c:?} {count}"))
+            .collect::<Vec<_>>();
+        rows.push(vec![
+            "Characters".to_string(),
+            format!("{} distinct: {}", histogram.len(), histogram.join(", ")),
+        ]);
+
+        let min = self.integers.iter().min();
+        let max = self.integers.iter().max();
+
+        if let (Some(min), Some(max)) = (min, max) {
+            let negatives = self.integers.iter().filter(|n| **n < 0).count();
+            let mut integers = format!("{}, min {min}, max {max}", self.integers.len());
+            if negatives > 0 {
+                integers += &format!(", ⚠ {negatives} negative");
+            }
+            rows.push(vec!["Integers".to_string(), integers]);
+
+            let fits = |n: i128| (0..=i128::from(u32::MAX)).contains(&n);
+            let check = |n: i128| {
+                if fits(n) {
+                    "✓ fit".to_string()
+                } else {
+                    format!("⚠ {n} does not fit")
+                }
+            };
+
+            let extreme = if fits(*min) { *max } else { *min };
+            rows.push(vec!["u32 values".to_string(), check(extreme)]);
+
+            if let Some((min, max)) = self.pair_product_range() {
+                let extreme = if fits(min) { max } else { min };
+                rows.push(vec!["u32 products".to_string(), check(extreme)]);
+            }
+        } else {
+            rows.push(vec!["Integers".to_string(), "none".to_string()]);
+        }
+
+        write!(f, "{}", output::table(&rows))
+    }
+}
+
+/// Returns all integers in the input. A `-` only counts as a sign if it does not follow a letter or digit,
+/// so that ranges like `1-3` are two positive integers.
+fn parse_integers(input: &str) -> Vec<i128> {
+    let mut integers = vec![];
+    let mut chars = input.char_indices().peekable();
+    let mut previous = None;
+
+    while let Some((start, c)) = chars.next() {
+        let is_sign = c == '-'
+            && !previous.is_some_and(char::is_alphanumeric)
+            && chars.peek().is_some_and(|(_, next)| next.is_ascii_digit());
+
+        if is_sign || c.is_ascii_digit() {
+            let mut end = start + c.len_utf8();
+            previous = Some(c);
+            while let Some((i, next)) = chars.next_if(|(_, next)| next.is_ascii_digit()) {
+                end = i + next.len_utf8();
+                previous = Some(next);
+            }
+
+            // integers too large even for `i128` are clamped, they do not fit `u32` either way.
+            let integer =
+                input[start..end]
+                    .parse()
+                    .unwrap_or(if is_sign { i128::MIN } else { i128::MAX });
+            integers.push(integer);
+        } else {
+            previous = Some(c);
+        }
+    }
+
+    integers
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_integers, Stats};
+
+    #[test]
+    fn parses_integers() {
+        assert_eq!(
+            parse_integers("Card 1: 41 -48 | x-3 1-3\n-7"),
+            vec![1, 41, -48, 3, 1, 3, -7]
+        );
+        assert_eq!(
+            parse_integers("99999999999999999999999999999999999999999"),
+            vec![i128::MAX]
+        );
+        assert!(parse_integers("no numbers - here").is_empty());
+    }
+
+    #[test]
+    fn collects_stats() {
+        let stats = Stats::collect("#..\n.#.\n..#\n");
+        assert!(stats.is_grid());
+        assert_eq!(stats.histogram, vec![('.', 6), ('#', 3)]);
+        assert!(stats.integers.is_empty());
+
+        let stats = Stats::collect("seeds: 79 14\n\n50 98 2\n52 50 48\n");
+        assert!(!stats.is_grid());
+        assert_eq!(stats.lines, 4);
+        assert_eq!(stats.sections, 2);
+        assert_eq!(stats.widths, (7, 12));
+        assert_eq!(stats.pair_product_range(), Some((2 * 14, 98 * 79)));
+    }
+
+    #[test]
+    fn flags_u32_overflows() {
+        assert_eq!(
+            Stats::collect("70000 -80000\n3\n").pair_product_range(),
+            Some((-5_600_000_000, 210_000))
+        );
+        assert_eq!(
+            Stats::collect("-70000 -80000 3").pair_product_range(),
+            Some((-240_000, 5_600_000_000))
+        );
+
+        let report = Stats::collect("70000 80000\n").to_string();
+        assert!(report.contains("u32 values    ✓ fit"));
+        assert!(report.contains("u32 products  ⚠ 5600000000 does not fit"));
+
+        let report = Stats::collect("70000 -80000 3\n").to_string();
+        assert!(report.contains("u32 products  ⚠ -5600000000 does not fit"));
+
+        // the largest product fits, but a negative one does not.
+        let report = Stats::collect("-1 1000 1000\n").to_string();
+        assert!(report.contains("u32 products  ⚠ -1000 does not fit"));
+
+        let report = Stats::collect("-5 10\n").to_string();
+        assert!(report.contains("⚠ 1 negative"));
+        assert!(report.contains("u32 values    ⚠ -5 does not fit"));
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,266 @@
+/// Shrinks a puzzle input while the variants of a solution disagree on the answer, using delta debugging.
+/// The solution is built once, after which its binary is run with `--all-variants` for every candidate input.
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::{env, fs};
+
+use crate::template::limits::Limits;
+use crate::template::runner::VARIANTS_DISAGREE;
+use crate::template::{output, path_for_part};
+use crate::Day;
+
+/// Candidates that run longer than this are considered not to reproduce the disagreement.
+const DEFAULT_MAX_CPU_TIME: u64 = 10;
+
+/// Flags passed to `minimize`.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub release: bool,
+    /// The part to minimize. Defaults to the first part whose variants disagree.
+    pub part: Option<u8>,
+    /// The input to start from. Defaults to the puzzle input of the day.
+    pub input: Option<String>,
+    pub limits: Limits,
+}
+
+pub fn handle(day: Day, options: &Options) {
+    let input_path = options.input.as_ref().map_or_else(
+        || path_for_part("inputs", day, options.part.unwrap_or(1)),
+        PathBuf::from,
+    );
+
+    let input = match fs::read_to_string(&input_path) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Failed to read input \"{}\": {e}", input_path.display());
+            process::exit(1);
+        }
+    };
+
+    if let Err(e) = build(day, options.release) {
+        eprintln!("Failed to build day {day}: {e}");
+        process::exit(1);
+    }
+
+    let mut limits = options.limits;
+    limits.max_cpu_time = limits.max_cpu_time.or(Some(DEFAULT_MAX_CPU_TIME));
+
+    let mut oracle = Oracle {
+        binary: binary_path(day, options.release),
+        scratch: env::temp_dir().join(format!("aoc-minimize-{day}.txt")),
+        part: options.part,
+        limits,
+        cache: HashMap::new(),
+    };
+
+    let Some(part) = oracle.disagreeing_part(&input) else {
+        eprintln!(
+            "The variants of day {day} agree on \"{}\", there is nothing to minimize.",
+            input_path.display()
+        );
+        process::exit(1);
+    };
+
+    oracle.part = Some(part);
+    output::status(format!(
+        "Minimizing \"{}\" while the variants of part {part} disagree...",
+        input_path.display()
+    ));
+
+    let minimized = minimize(&input, |candidate| {
+        oracle.disagreeing_part(candidate).is_some()
+    });
+
+    output::status(format!(
+        "Reduced {} lines to {} lines in {} runs.",
+        input.lines().count(),
+        minimized.lines().count(),
+        oracle.cache.len()
+    ));
+
+    let example_path = example_path(day);
+    if let Err(e) = fs::write(&example_path, &minimized) {
+        eprintln!("Failed to write minimized input: {e}");
+        process::exit(1);
+    }
+
+    let _ = fs::remove_file(&oracle.scratch);
+
+    output::status("---");
+    output::status(format!(
+        "🎄 Wrote minimized input to \"{}\".",
+        example_path.display()
+    ));
+    output::detail(minimized);
+}
+
+/// Runs the variants of a solution on candidate inputs, caching the results.
+struct Oracle {
+    binary: PathBuf,
+    /// File that candidate inputs are written to.
+    scratch: PathBuf,
+    /// The part whose variants need to disagree, or any part if [`None`].
+    part: Option<u8>,
+    limits: Limits,
+    cache: HashMap<String, Option<u8>>,
+}
+
+impl Oracle {
+    /// Returns the first part whose variants disagree on `input`.
+    fn disagreeing_part(&mut self, input: &str) -> Option<u8> {
+        if let Some(part) = self.cache.get(input) {
+            return *part;
+        }
+
+        let part = self.run(input);
+        self.cache.insert(input.to_string(), part);
+        part
+    }
+
+    fn run(&self, input: &str) -> Option<u8> {
+        fs::write(&self.scratch, input).ok()?;
+
+        let mut cmd = Command::new(&self.binary);
+        cmd.args(["--all-variants", "--quiet", "--input"])
+            .arg(&self.scratch)
+            .args(self.limits.to_args());
+
+        if let Some(part) = self.part {
+            cmd.args(["--part", &part.to_string()]);
+        }
+
+        output::detail(format!("Trying {} lines", input.lines().count()));
+
+        // candidates that make a variant panic are expected, so their errors are not shown.
+        let result = cmd.stderr(Stdio::null()).output().ok()?;
+
+        String::from_utf8_lossy(&result.stdout)
+            .lines()
+            .filter(|line| line.contains(VARIANTS_DISAGREE))
+            .find_map(|line| line.strip_prefix("Part ")?.split(':').next()?.parse().ok())
+    }
+}
+
+fn build(day: Day, release: bool) -> Result<(), String> {
+    let mut cmd = Command::new("cargo");
+    cmd.args(["build", "--quiet", "--bin", &day.to_string()]);
+
+    if release {
+        cmd.arg("--release");
+    }
+
+    match cmd.status() {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => Err(format!("cargo exited with {status}")),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn binary_path(day: Day, release: bool) -> PathBuf {
+    let target_dir = env::var_os("CARGO_TARGET_DIR").map_or_else(|| "target".into(), PathBuf::from);
+    let profile = if release { "release" } else { "debug" };
+    target_dir
+        .join(profile)
+        .join(format!("{day}{}", env::consts::EXE_SUFFIX))
+}
+
+/// Returns the first unused example path, e.g. `data/examples/05-minimized.txt`.
+fn example_path(day: Day) -> PathBuf {
+    let folder = Path::new("data").join("examples");
+
+    (1..)
+        .map(|n| match n {
+            1 => folder.join(format!("{day}-minimized.txt")),
+            n => folder.join(format!("{day}-minimized-{n}.txt")),
+        })
+        .find(|path| !path.exists())
+        .unwrap()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Minimizes an input by first removing whole sections (separated by blank lines), then single lines.
+fn minimize(input: &str, mut test: impl FnMut(&str) -> bool) -> String {
+    let input = input.trim_end_matches('\n');
+
+    let sections = input.split("\n\n").collect::<Vec<_>>();
+    let sections = ddmin(&sections, |s| test(&(s.join("\n\n") + "\n")));
+    let text = sections.join("\n\n");
+
+    let lines = text.lines().collect::<Vec<_>>();
+    let lines = ddmin(&lines, |l| test(&(l.join("\n") + "\n")));
+
+    lines.join("\n") + "\n"
+}
+
+/// Delta debugging: returns a subsequence of `items` that still passes `test` and from which no single chunk can be removed.
+/// `test` is assumed to pass for `items`.
+fn ddmin<T: Clone>(items: &[T], mut test: impl FnMut(&[T]) -> bool) -> Vec<T> {
+    let mut items = items.to_vec();
+    let mut granularity = 2;
+
+    while items.len() >= 2 {
+        let chunk_size = items.len().div_ceil(granularity);
+        let chunks = (0..items.len())
+            .step_by(chunk_size)
+            .map(|start| start..(start + chunk_size).min(items.len()))
+            .collect::<Vec<Range<usize>>>();
+
+        if let Some(chunk) = chunks.iter().find(|c| test(&items[(*c).clone()])) {
+            items = items[chunk.clone()].to_vec();
+            granularity = 2;
+            continue;
+        }
+
+        if let Some(complement) = chunks
+            .iter()
+            .map(|c| [&items[..c.start], &items[c.end..]].concat())
+            .find(|complement| test(complement))
+        {
+            items = complement;
+            granularity = (granularity - 1).max(2);
+            continue;
+        }
+
+        if granularity >= items.len() {
+            break;
+        }
+
+        granularity = (granularity * 2).min(items.len());
+    }
+
+    items
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ddmin, minimize};
+
+    #[test]
+    fn ddmin_finds_minimal_subset() {
+        let items = (0..100).collect::<Vec<_>>();
+        let result = ddmin(&items, |s| s.contains(&3) && s.contains(&42));
+        assert_eq!(result, vec![3, 42]);
+
+        // no single item can be removed from the result.
+        let test = |s: &[i32]| s.iter().sum::<i32>() >= 195;
+        let result = ddmin(&items, test);
+        assert!(test(&result));
+        for i in 0..result.len() {
+            assert!(!test(&[&result[..i], &result[i + 1..]].concat()));
+        }
+    }
+
+    #[test]
+    fn minimizes_sections_then_lines() {
+        let input = "header\n1\n2\n\nmap a:\n3\n4\n\nmap b:\n5\n6\n";
+        let result = minimize(input, |s| s.contains("header") && s.contains("4"));
+        assert_eq!(result, "header\n4\n");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
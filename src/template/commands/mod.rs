@@ -1,5 +1,10 @@
 pub mod all;
+pub mod analyze;
+pub mod compare;
 pub mod download;
+pub mod inspect;
+pub mod minimize;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod watch;
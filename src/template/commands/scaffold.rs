@@ -4,6 +4,7 @@ use std::{
     process,
 };
 
+use crate::template::output;
 use crate::Day;
 
 const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
@@ -39,7 +40,11 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
 pub fn handle(day: Day) {
@@ -61,7 +66,7 @@ pub fn handle(day: Day) {
             .as_bytes(),
     ) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            output::status(format!("Created module file \"{}\"", &module_path));
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -71,7 +76,7 @@ pub fn handle(day: Day) {
 
     match create_file(&input_path) {
         Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+            output::status(format!("Created empty input file \"{}\"", &input_path));
         }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
@@ -81,7 +86,7 @@ pub fn handle(day: Day) {
 
     match create_file(&example_path) {
         Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+            output::status(format!("Created empty example file \"{}\"", &example_path));
         }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
@@ -89,6 +94,6 @@ pub fn handle(day: Day) {
         }
     }
 
-    println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    output::status("---");
+    output::status(format!("🎄 Type `cargo solve {day}` to run your solution."));
 }
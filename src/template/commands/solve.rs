@@ -1,31 +1,138 @@
-use std::process::{Command, Stdio};
+use std::io::{BufRead, BufReader};
+use std::process::{self, Command, Stdio};
 
+use crate::template::limits::Limits;
+use crate::template::{machine, output};
 use crate::Day;
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
+/// Flags passed to `solve`.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub release: bool,
+    pub time: bool,
+    pub track_allocs: bool,
+    pub limits: Limits,
+    pub pin_cpu: Option<usize>,
+    pub part: Option<u8>,
+    pub submit_part: Option<u8>,
+    /// Run this variant instead of the first one.
+    pub variant: Option<String>,
+    /// Run all variants and check that they agree on the answers.
+    pub all_variants: bool,
+    /// Read the puzzle input from this file instead of `data/inputs`.
+    pub input: Option<String>,
+    /// Print the values recorded via `explain!` after each part.
+    pub explain: bool,
+}
+
+pub fn handle(day: Day, options: &Options) {
+    let Options {
+        release,
+        time,
+        track_allocs,
+        limits,
+        pin_cpu,
+        part,
+        submit_part,
+        variant,
+        all_variants,
+        input,
+        explain,
+    } = options.clone();
+
+    if time {
+        machine::warn_about_governor(pin_cpu);
+    }
+
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
+    // cargo's stderr is piped below, so it only keeps its colors if asked to.
+    if output::use_color() {
+        cmd_args.push("--color=always".to_string());
+    }
+
+    if output::is_quiet() {
+        cmd_args.push("--quiet".to_string());
+    }
+
     if release {
         cmd_args.push("--release".to_string());
     }
 
+    let features = [("track_allocs", track_allocs), ("explain", explain)]
+        .iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(feature, _)| *feature)
+        .collect::<Vec<_>>();
+
+    if !features.is_empty() {
+        cmd_args.push("--features".to_string());
+        cmd_args.push(features.join(","));
+    }
+
     cmd_args.push("--".to_string());
 
+    if let Some(part) = part {
+        cmd_args.push("--part".to_string());
+        cmd_args.push(part.to_string());
+    }
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
+    if let Some(variant) = variant {
+        cmd_args.push("--variant".to_string());
+        cmd_args.push(variant);
+    }
+
+    if all_variants {
+        cmd_args.push("--all-variants".to_string());
+    }
+
+    if let Some(input) = input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input);
+    }
+
     if time {
         cmd_args.push("--time".to_string());
     }
 
+    if let Some(cpu) = pin_cpu {
+        cmd_args.push("--pin-cpu".to_string());
+        cmd_args.push(cpu.to_string());
+    }
+
+    cmd_args.extend(limits.to_args());
+    cmd_args.extend(output::verbosity().to_args());
+
+    output::detail(format!("Running `cargo {}`", cmd_args.join(" ")));
+
+    // stderr is forwarded line by line so that exceeded resource limits can be detected.
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
+        .stderr(Stdio::piped())
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let stderr = BufReader::new(cmd.stderr.take().unwrap())
+        .lines()
+        .map_while(Result::ok)
+        .inspect(|line| eprintln!("{line}"))
+        .collect::<Vec<String>>();
+
+    let status = cmd.wait().unwrap();
+
+    if let Some(exceeded) = limits.exceeded(&status, &stderr) {
+        eprintln!("⚠ {exceeded}");
+        process::exit(1);
+    }
+
+    // e.g. a part failed or variants disagree.
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
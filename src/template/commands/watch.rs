@@ -0,0 +1,218 @@
+/// Re-runs the example tests and the solution of a day whenever its sources or data files change.
+/// Files are polled for changes, so that no file watching dependency is needed.
+use std::collections::BTreeMap;
+use std::io::{stdout, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use std::{fs, process};
+
+use crate::template::{output, path_for_part, read_data_file};
+use crate::Day;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Editors often write a file in several steps, so changes are only picked up once files have been quiet for this long.
+const SETTLE_TIME: Duration = Duration::from_millis(100);
+
+/// Flags passed to `watch`.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub release: bool,
+}
+
+pub fn handle(day: Day, options: &Options) {
+    if !Path::new(&super::all::get_path_for_bin(day)).exists() {
+        eprintln!("Day {day} has not been scaffolded yet, try `cargo scaffold {day}`.");
+        process::exit(1);
+    }
+
+    let mut snapshot = BTreeMap::new();
+
+    loop {
+        let current = modification_times(day);
+
+        if current != snapshot {
+            thread::sleep(SETTLE_TIME);
+            snapshot = modification_times(day);
+            run(day, options);
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Returns the day's solution, its data files and the library sources, which solutions depend on.
+fn watched_files(day: Day) -> Vec<PathBuf> {
+    let mut files = vec![PathBuf::from("Cargo.toml")];
+    collect_sources(Path::new("src"), &mut files);
+    files.push(Path::new("src").join("bin").join(format!("{day}.rs")));
+
+    for folder in ["examples", "inputs"] {
+        let Ok(entries) = fs::read_dir(Path::new("data").join(folder)) else {
+            continue;
+        };
+
+        files.extend(
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .is_some_and(|name| name.to_string_lossy().starts_with(&day.to_string()))
+                }),
+        );
+    }
+
+    files
+}
+
+/// Collects all Rust sources below `dir`, except for the solutions in `src/bin`.
+fn collect_sources(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+        if path.is_dir() && path != Path::new("src").join("bin") {
+            collect_sources(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+fn modification_times(day: Day) -> BTreeMap<PathBuf, SystemTime> {
+    watched_files(day)
+        .into_iter()
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, modified))
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn run(day: Day, options: &Options) {
+    if output::is_terminal() {
+        // clear the screen and move the cursor to the top left.
+        print!("\x1b[2J\x1b[H");
+        let _ = stdout().flush();
+    }
+
+    output::status(output::bold(format!("Day {day}")));
+    output::status("------");
+
+    if !run_tests(day) {
+        // nothing to run.
+    } else if read_data_file(&path_for_part("inputs", day, 1)).is_err() {
+        println!("Input: ✖ missing, try `cargo download {day}`");
+    } else {
+        run_solution(day, options);
+    }
+
+    output::status(output::italic(format!(
+        "\nWatching for changes to day {day}, press Ctrl-C to stop."
+    )));
+}
+
+/// Runs the tests of a day and prints a summary, or the full output if they failed.
+/// Returns whether the solution compiled.
+fn run_tests(day: Day) -> bool {
+    let args = ["test", "--quiet", "--bin", &day.to_string()];
+    output::detail(format!("Running `cargo {}`", args.join(" ")));
+
+    let result = match Command::new("cargo").args(args).output() {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("Failed to run tests: {e}");
+            return false;
+        }
+    };
+
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    let stderr = String::from_utf8_lossy(&result.stderr);
+
+    let Some(summary) = stdout.lines().find(|line| line.starts_with("test result:")) else {
+        // the solution did not compile.
+        println!("Tests: ⚠ failed to compile");
+        eprintln!("{}", stderr.trim_end());
+        return false;
+    };
+
+    if result.status.success() {
+        // e.g. `test result: ok. 3 passed; 0 failed; ...`
+        let passed = summary
+            .trim_start_matches("test result: ok. ")
+            .split(';')
+            .next()
+            .unwrap_or_default();
+        println!("Tests: ✓ {passed}");
+    } else {
+        println!("Tests: ✗ failed");
+        println!("{}", stdout.trim_end());
+    }
+
+    true
+}
+
+fn run_solution(day: Day, options: &Options) {
+    let mut args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+    ];
+
+    if options.release {
+        args.push("--release".to_string());
+    }
+
+    args.push("--".to_string());
+    args.extend(output::verbosity().to_args());
+
+    output::detail(format!("Running `cargo {}`", args.join(" ")));
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(&args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit());
+    output::forward_terminal(&mut cmd);
+
+    if let Err(e) = cmd.status() {
+        eprintln!("Failed to run day {day}: {e}");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::watched_files;
+    use crate::Day;
+    use std::path::PathBuf;
+
+    #[test]
+    fn watches_day_and_library_sources() {
+        let files = watched_files(Day::new(1).unwrap());
+
+        for path in [
+            "src/bin/01.rs",
+            "src/lib.rs",
+            "src/template/mod.rs",
+            "data/examples/01-2.txt",
+        ] {
+            assert!(
+                files.contains(&PathBuf::from(path)),
+                "{path} is not watched"
+            );
+        }
+
+        assert!(!files.contains(&PathBuf::from("src/bin/02.rs")));
+        assert!(!files.contains(&PathBuf::from("data/examples/02.txt")));
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,196 @@
+/// The context a solution part runs in: whether its input is an example, and named parameters of that input.
+/// Many puzzles use different constants for the examples than for the real input, e.g. a grid size or a step count.
+/// These are read from a `.params` file next to the input, e.g. `data/examples/14.params` for `data/examples/14.txt`:
+///
+/// ```text
+/// # the example is a smaller grid.
+/// size = 11
+/// steps = 6
+/// ```
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::template::{path_for_part, read_data_file};
+use crate::Day;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Context {
+    is_example: bool,
+    params: HashMap<String, String>,
+    /// The file the parameters were read from, for error messages.
+    source: Option<PathBuf>,
+}
+
+impl Context {
+    /// Returns the context of an input file. Files in an `examples` folder are examples.
+    /// Parameters are read from the `.params` file with the same name, falling back to the file shared by all parts,
+    /// e.g. `01.params` for `01-2.txt`. Inputs without a `.params` file have no parameters.
+    ///
+    /// # Panics
+    /// If the `.params` file is malformed.
+    #[must_use]
+    pub fn for_file(path: &Path) -> Self {
+        let is_example = path
+            .parent()
+            .and_then(Path::file_name)
+            .is_some_and(|folder| folder == "examples");
+
+        let Some((source, contents)) = params_paths(path)
+            .into_iter()
+            .find_map(|source| Some((source.clone(), read_data_file(&source).ok()?)))
+        else {
+            return Self {
+                is_example,
+                ..Self::default()
+            };
+        };
+
+        let params = parse_params(&contents)
+            .unwrap_or_else(|e| panic!("invalid \"{}\": {e}", source.display()));
+
+        Self {
+            is_example,
+            params,
+            source: Some(source),
+        }
+    }
+
+    /// Returns the context of the example of a part, e.g. `data/examples/01-2.txt`, for use in tests.
+    #[must_use]
+    pub fn example(day: Day, part: u8) -> Self {
+        Self::for_file(&path_for_part("examples", day, part))
+    }
+
+    #[must_use]
+    pub fn is_example(&self) -> bool {
+        self.is_example
+    }
+
+    /// Returns the parameter called `name`, or `default` if the input does not set it.
+    /// Defaults are usually the values of the real input, so that only examples need a `.params` file.
+    ///
+    /// # Panics
+    /// If the parameter is set to a value that does not parse as `T`.
+    pub fn param<T: FromStr>(&self, name: &str, default: T) -> T
+    where
+        T::Err: Display,
+    {
+        let Some(value) = self.params.get(name) else {
+            return default;
+        };
+
+        value.parse().unwrap_or_else(|e| {
+            let source = self.source.as_deref().unwrap_or(Path::new("params"));
+            panic!(
+                "invalid parameter `{name} = {value}` in \"{}\": {e}",
+                source.display()
+            )
+        })
+    }
+}
+
+/// Returns the `.params` files that may hold the parameters of an input, most specific first.
+fn params_paths(path: &Path) -> Vec<PathBuf> {
+    let mut paths = vec![path.with_extension("params")];
+
+    // only inputs of a part, e.g. `01-2.txt`, share the file of the day. Others, e.g. `05-minimized.txt`, do not.
+    if let Some((day, _)) = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .and_then(|stem| stem.split_once('-'))
+        .filter(|(_, part)| matches!(*part, "1" | "2"))
+    {
+        paths.push(path.with_file_name(format!("{day}.params")));
+    }
+
+    paths
+}
+
+/// Parses one `name = value` per line. Empty lines and lines starting with `#` are ignored.
+fn parse_params(s: &str) -> Result<HashMap<String, String>, String> {
+    let mut params = HashMap::new();
+
+    for (i, line) in s.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let Some((name, value)) = line
+            .split_once('=')
+            .filter(|(name, _)| !name.trim().is_empty())
+        else {
+            return Err(format!(
+                "line {}: expecting a parameter like `steps = 64`, found `{line}`",
+                i + 1
+            ));
+        };
+
+        params.insert(name.trim().to_string(), value.trim().to_string());
+    }
+
+    Ok(params)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{params_paths, parse_params, Context};
+    use std::path::{Path, PathBuf};
+    use std::{env, fs};
+
+    #[test]
+    fn parses_params() {
+        let params = parse_params("# comment\n\nsize = 11\nname=  a b \n").unwrap();
+        assert_eq!(params.len(), 2);
+        assert_eq!(params["size"], "11");
+        assert_eq!(params["name"], "a b");
+
+        assert!(parse_params("size 11").unwrap_err().starts_with("line 1:"));
+        assert!(parse_params(" = 11").is_err());
+    }
+
+    #[test]
+    fn finds_params_files() {
+        assert_eq!(
+            params_paths(Path::new("data/examples/01-2.txt")),
+            vec![
+                PathBuf::from("data/examples/01-2.params"),
+                PathBuf::from("data/examples/01.params")
+            ]
+        );
+        assert_eq!(
+            params_paths(Path::new("data/inputs/01.txt")),
+            vec![PathBuf::from("data/inputs/01.params")]
+        );
+        assert_eq!(
+            params_paths(Path::new("data/inputs/05-minimized.txt")),
+            vec![PathBuf::from("data/inputs/05-minimized.params")]
+        );
+    }
+
+    #[test]
+    fn reads_params_of_inputs() {
+        let examples = env::temp_dir().join("aoc-context").join("examples");
+        fs::create_dir_all(&examples).unwrap();
+        fs::write(examples.join("14.params"), "size = 11\n").unwrap();
+
+        let context = Context::for_file(&examples.join("14-2.txt"));
+        assert!(context.is_example());
+        assert_eq!(context.param("size", 101), 11);
+        assert_eq!(context.param("steps", 64), 64);
+
+        let context = Context::for_file(&examples.join("14-minimized.txt"));
+        assert!(context.is_example());
+        assert_eq!(context.param("size", 101), 101);
+
+        let context = Context::for_file(Path::new("data/inputs/99.txt"));
+        assert!(!context.is_example());
+        assert_eq!(context, Context::default());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,67 @@
+/// Parsing of durations, shared by the timings printed by solutions, `budgets.txt` and `--max-cpu-time`,
+/// so that all of them accept the same units.
+use std::time::Duration;
+
+/// Parses a duration like `100ms`, `1.5s`, `250µs` or `2h` into nanoseconds.
+/// This includes durations as printed by solutions, e.g. `12.3µs` or `74.13ns`.
+pub fn parse_nanos(s: &str) -> Result<f64, String> {
+    let error = || format!("expecting a duration like `100ms` or `1.5s`, found `{s}`");
+
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .ok_or_else(error)?;
+    let (number, unit) = s.split_at(split);
+
+    let number = number.parse::<f64>().map_err(|_| error())?;
+    let multiplier = match unit {
+        "ns" => 1.0,
+        "µs" | "us" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        "m" => 60e9,
+        "h" => 3600e9,
+        _ => return Err(error()),
+    };
+
+    Ok(number * multiplier)
+}
+
+/// Parses a duration like [`parse_nanos`], rounded to whole nanoseconds.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    parse_nanos(s).map(from_nanos)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn from_nanos(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.max(0.0).round() as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_duration, parse_nanos};
+    use std::time::Duration;
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("100ms"), Ok(Duration::from_millis(100)));
+        assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("250µs"), Ok(Duration::from_micros(250)));
+        assert_eq!(parse_duration("250us"), Ok(Duration::from_micros(250)));
+        assert_eq!(parse_duration("2m"), Ok(Duration::from_secs(120)));
+        assert_eq!(parse_duration("1h"), Ok(Duration::from_secs(3600)));
+        assert!(parse_duration("100").is_err());
+        assert!(parse_duration("ms").is_err());
+        assert!(parse_duration("-1s").is_err());
+        assert!(parse_duration("1d").is_err());
+    }
+
+    #[test]
+    fn keeps_fractional_nanos() {
+        assert_eq!(parse_nanos("74.13ns"), Ok(74.13));
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,149 @@
+/// Records labelled per-item values while a solution runs, e.g. the points of every card, to explain how an answer adds up.
+/// Values are only recorded with the `explain` feature, without it [`explain!`](crate::explain!) compiles to nothing.
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crate::template::output;
+
+static RECORDING: AtomicBool = AtomicBool::new(false);
+static ROWS: Mutex<Vec<Row>> = Mutex::new(Vec::new());
+
+/// The values recorded for one item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub item: String,
+    pub values: Vec<(String, String)>,
+}
+
+/// Records labelled values for an item, if the `explain` feature is enabled:
+///
+/// ```ignore
+/// advent_of_code::explain!(format!("Card {id}"), "matches" => matches, "points" => points);
+/// ```
+///
+/// The values are not evaluated without the feature, so they must not have side effects.
+#[macro_export]
+macro_rules! explain {
+    ($item:expr, $($label:expr => $value:expr),+ $(,)?) => {{
+        #[cfg(feature = "explain")]
+        $crate::template::explain::record(
+            $item,
+            &[$(($label, &$value as &dyn ::std::fmt::Display)),+],
+        );
+        #[cfg(not(feature = "explain"))]
+        let _ = || {
+            let _ = (&$item, $(&$value),+);
+        };
+    }};
+}
+
+/// Records the values of an item. Does nothing outside of [`start`] and [`stop`].
+pub fn record(item: impl Display, values: &[(&str, &dyn Display)]) {
+    if !RECORDING.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let row = Row {
+        item: item.to_string(),
+        values: values
+            .iter()
+            .map(|(label, value)| ((*label).to_string(), value.to_string()))
+            .collect(),
+    };
+
+    ROWS.lock().unwrap().push(row);
+}
+
+/// Discards previously recorded rows and starts recording.
+pub fn start() {
+    ROWS.lock().unwrap().clear();
+    RECORDING.store(true, Ordering::Relaxed);
+}
+
+/// Stops recording, e.g. before a part is benched.
+pub fn stop() {
+    RECORDING.store(false, Ordering::Relaxed);
+}
+
+/// Returns the rows recorded since the last [`start`].
+pub fn take() -> Vec<Row> {
+    std::mem::take(&mut *ROWS.lock().unwrap())
+}
+
+/// Formats rows as a table with one column per label, in the order labels were first recorded.
+/// Cells of labels that an item did not record are left empty.
+pub fn format_table(rows: &[Row]) -> String {
+    let mut labels: Vec<&str> = vec![];
+    for (label, _) in rows.iter().flat_map(|row| &row.values) {
+        if !labels.contains(&label.as_str()) {
+            labels.push(label);
+        }
+    }
+
+    let header = std::iter::once("item")
+        .chain(labels.iter().copied())
+        .map(String::from)
+        .collect();
+
+    let cells = rows.iter().map(|row| {
+        std::iter::once(row.item.clone())
+            .chain(labels.iter().map(|label| {
+                row.values
+                    .iter()
+                    .find(|(l, _)| l == label)
+                    .map_or_else(String::new, |(_, value)| value.clone())
+            }))
+            .collect()
+    });
+
+    output::table(&std::iter::once(header).chain(cells).collect::<Vec<_>>())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_table, record, start, stop, take, Row};
+
+    fn row(item: &str, values: &[(&str, &str)]) -> Row {
+        Row {
+            item: item.into(),
+            values: values
+                .iter()
+                .map(|(l, v)| ((*l).into(), (*v).into()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn records_only_while_started() {
+        record("Card 0", &[("points", &1)]);
+        start();
+        record("Card 1", &[("matches", &4), ("points", &8)]);
+        stop();
+        record("Card 2", &[("points", &0)]);
+
+        assert_eq!(
+            take(),
+            vec![row("Card 1", &[("matches", "4"), ("points", "8")])]
+        );
+        assert!(take().is_empty());
+    }
+
+    #[test]
+    fn formats_table() {
+        let rows = vec![
+            row("Card 1", &[("matches", "4"), ("points", "8")]),
+            row("Card 2", &[("matches", "0")]),
+            row("Card 10", &[("points", "1"), ("matches", "1")]),
+        ];
+
+        assert_eq!(
+            format_table(&rows),
+            "item     matches  points\nCard 1   4        8\nCard 2   0\nCard 10  1        1"
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
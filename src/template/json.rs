@@ -0,0 +1,264 @@
+/// A minimal JSON reader for the messages of `cargo build --message-format=json`, so that the template
+/// does not depend on `serde_json` for the few fields it needs.
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    /// Members in the order they appear in.
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Parses a single JSON value, e.g. one line of cargo's output.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            s,
+            chars: s.char_indices().peekable(),
+        };
+
+        let value = parser.value()?;
+        parser.skip_whitespace();
+
+        match parser.chars.next() {
+            None => Ok(value),
+            Some((i, c)) => Err(format!("unexpected `{c}` at {i}")),
+        }
+    }
+
+    /// Returns the member `key` of an object.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(members) => members.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+struct Parser<'a> {
+    s: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl Parser<'_> {
+    fn value(&mut self) -> Result<Value, String> {
+        self.skip_whitespace();
+
+        match self.chars.peek().copied() {
+            Some((_, '{')) => self.object(),
+            Some((_, '[')) => self.array(),
+            Some((_, '"')) => self.string().map(Value::String),
+            Some((_, 't')) => self.keyword("true", Value::Bool(true)),
+            Some((_, 'f')) => self.keyword("false", Value::Bool(false)),
+            Some((_, 'n')) => self.keyword("null", Value::Null),
+            Some((_, c)) if c == '-' || c.is_ascii_digit() => self.number(),
+            Some((i, c)) => Err(format!("unexpected `{c}` at {i}")),
+            None => Err("unexpected end of input".into()),
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, String> {
+        self.expect('{')?;
+        let mut members = vec![];
+
+        self.skip_whitespace();
+        if self.chars.next_if(|(_, c)| *c == '}').is_some() {
+            return Ok(Value::Object(members));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            members.push((key, self.value()?));
+
+            self.skip_whitespace();
+            if self.chars.next_if(|(_, c)| *c == ',').is_none() {
+                self.expect('}')?;
+                return Ok(Value::Object(members));
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, String> {
+        self.expect('[')?;
+        let mut values = vec![];
+
+        self.skip_whitespace();
+        if self.chars.next_if(|(_, c)| *c == ']').is_some() {
+            return Ok(Value::Array(values));
+        }
+
+        loop {
+            values.push(self.value()?);
+
+            self.skip_whitespace();
+            if self.chars.next_if(|(_, c)| *c == ',').is_none() {
+                self.expect(']')?;
+                return Ok(Value::Array(values));
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut s = String::new();
+
+        loop {
+            match self.chars.next() {
+                Some((_, '"')) => return Ok(s),
+                Some((_, '\\')) => s.push(self.escape()?),
+                Some((_, c)) => s.push(c),
+                None => return Err("unterminated string".into()),
+            }
+        }
+    }
+
+    fn escape(&mut self) -> Result<char, String> {
+        match self.chars.next() {
+            Some((_, 'n')) => Ok('\n'),
+            Some((_, 't')) => Ok('\t'),
+            Some((_, 'r')) => Ok('\r'),
+            Some((_, 'b')) => Ok('\u{8}'),
+            Some((_, 'f')) => Ok('\u{c}'),
+            Some((_, 'u')) => {
+                let unit = self.hex4()?;
+
+                // characters outside the basic multilingual plane are escaped as a surrogate pair.
+                let code = if (0xD800..0xDC00).contains(&unit) {
+                    self.expect('\\')?;
+                    self.expect('u')?;
+                    let low = self.hex4()?;
+                    0x10000 + ((unit - 0xD800) << 10) + low.wrapping_sub(0xDC00)
+                } else {
+                    unit
+                };
+
+                Ok(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER))
+            }
+            Some((_, c)) => Ok(c),
+            None => Err("unterminated string".into()),
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, String> {
+        (0..4).try_fold(0, |code, _| match self.chars.next() {
+            Some((_, c)) if c.is_ascii_hexdigit() => Ok(code * 16 + c.to_digit(16).unwrap()),
+            _ => Err("invalid unicode escape".to_string()),
+        })
+    }
+
+    fn number(&mut self) -> Result<Value, String> {
+        let start = self.chars.peek().map_or(self.s.len(), |(i, _)| *i);
+        let mut end = start;
+
+        while let Some((i, c)) = self
+            .chars
+            .next_if(|(_, c)| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+        {
+            end = i + c.len_utf8();
+        }
+
+        let number = &self.s[start..end];
+        number
+            .parse()
+            .map(Value::Number)
+            .map_err(|_| format!("invalid number `{number}` at {start}"))
+    }
+
+    fn keyword(&mut self, keyword: &str, value: Value) -> Result<Value, String> {
+        for expected in keyword.chars() {
+            self.expect(expected)?;
+        }
+        Ok(value)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.chars.next() {
+            Some((_, c)) if c == expected => Ok(()),
+            Some((i, c)) => Err(format!("expecting `{expected}`, found `{c}` at {i}")),
+            None => Err(format!("expecting `{expected}`, found end of input")),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Value;
+
+    #[test]
+    fn parses_values() {
+        let value =
+            Value::parse(r#" {"a": [1, -2.5e3, true, null], "b": {"c": "d"}, "e": []} "#).unwrap();
+
+        assert_eq!(
+            value.get("a").and_then(Value::as_array),
+            Some(
+                &[
+                    Value::Number(1.0),
+                    Value::Number(-2500.0),
+                    Value::Bool(true),
+                    Value::Null
+                ][..]
+            )
+        );
+        assert_eq!(
+            value
+                .get("b")
+                .and_then(|b| b.get("c"))
+                .and_then(Value::as_str),
+            Some("d")
+        );
+        assert_eq!(value.get("e"), Some(&Value::Array(vec![])));
+        assert_eq!(value.get("f"), None);
+    }
+
+    #[test]
+    fn parses_escapes() {
+        let value = Value::parse(r#""a\"b\\c\nd\u001b[1mé\ud83c\udf84""#).unwrap();
+        assert_eq!(value.as_str(), Some("a\"b\\c\nd\u{1b}[1mé🎄"));
+    }
+
+    #[test]
+    fn rejects_malformed_json() {
+        assert!(Value::parse(r#"{"a": 1"#).is_err());
+        assert!(Value::parse(r#"{"a" 1}"#).is_err());
+        assert!(Value::parse(r#""unterminated"#).is_err());
+        assert!(Value::parse("[1, 2] 3").is_err());
+        assert!(Value::parse("nul").is_err());
+        assert!(Value::parse("").is_err());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,333 @@
+/// Resource limits for solution processes.
+/// The `solve` and `all` commands forward `--max-memory`, `--max-cpu-time` and `--stack-size` to the solution binary,
+/// which applies them to itself before reading its input. Memory and CPU time limits are only enforced on Linux.
+use std::env;
+use std::fmt::Display;
+use std::process::ExitStatus;
+
+use crate::template::duration::parse_duration;
+
+/// Printed to stderr by a solution process when it reaches its CPU time limit, see [`Limits::apply`].
+const CPU_TIME_EXCEEDED: &str = "cpu time limit reached";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Maximum address space in bytes.
+    pub max_memory: Option<u64>,
+    /// Maximum CPU time in seconds.
+    pub max_cpu_time: Option<u64>,
+    /// Stack size in bytes of the threads that run the parts, see [`crate::template::runner::init_stack_size`].
+    pub stack_size: Option<u64>,
+}
+
+/// The resource a solution process ran out of.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LimitExceeded {
+    Memory,
+    CpuTime,
+    /// A part overflowed the stack of its thread. Holds the name of the part, e.g. `Part 1`, if it is known.
+    Stack(Option<String>),
+}
+
+impl Display for LimitExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LimitExceeded::Memory => write!(f, "resource limit exceeded: memory"),
+            LimitExceeded::CpuTime => write!(f, "resource limit exceeded: cpu time"),
+            LimitExceeded::Stack(Some(part)) => {
+                write!(f, "stack overflow in {part}, try a larger `--stack-size`")
+            }
+            LimitExceeded::Stack(None) => {
+                write!(f, "stack overflow, try a larger `--stack-size`")
+            }
+        }
+    }
+}
+
+impl Limits {
+    /// Returns the arguments that pass these limits on to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(bytes) = self.max_memory {
+            args.push("--max-memory".into());
+            args.push(bytes.to_string());
+        }
+
+        if let Some(seconds) = self.max_cpu_time {
+            args.push("--max-cpu-time".into());
+            args.push(seconds.to_string());
+        }
+
+        if let Some(bytes) = self.stack_size {
+            args.push("--stack-size".into());
+            args.push(bytes.to_string());
+        }
+
+        args
+    }
+
+    /// Reads the limits passed to the current solution binary.
+    pub fn from_args() -> Result<Self, String> {
+        let args: Vec<String> = env::args().collect();
+
+        let value = |name: &str| -> Option<&str> {
+            let index = args.iter().position(|x| x == name)?;
+            args.get(index + 1).map(String::as_str)
+        };
+
+        Ok(Self {
+            max_memory: value("--max-memory").map(parse_bytes).transpose()?,
+            max_cpu_time: value("--max-cpu-time").map(parse_seconds).transpose()?,
+            stack_size: value("--stack-size").map(parse_bytes).transpose()?,
+        })
+    }
+
+    /// Applies the limits to the current process. The stack size is applied by the runner instead.
+    #[cfg(target_os = "linux")]
+    pub fn apply(&self) -> Result<(), String> {
+        let set = |resource, soft, hard| {
+            let limit = libc::rlimit {
+                rlim_cur: soft,
+                rlim_max: hard,
+            };
+
+            // SAFETY: `limit` is a valid, initialized rlimit struct.
+            if unsafe { libc::setrlimit(resource, &limit) } == 0 {
+                Ok(())
+            } else {
+                Err(std::io::Error::last_os_error().to_string())
+            }
+        };
+
+        if let Some(bytes) = self.max_memory {
+            set(libc::RLIMIT_AS, bytes, bytes)?;
+        }
+
+        if let Some(seconds) = self.max_cpu_time {
+            // the soft limit sends SIGXCPU, the hard limit a second later sends SIGKILL.
+            set(libc::RLIMIT_CPU, seconds, seconds + 1)?;
+
+            // SAFETY: the handler only calls async-signal-safe functions.
+            unsafe {
+                libc::signal(
+                    libc::SIGXCPU,
+                    on_cpu_time_exceeded as extern "C" fn(libc::c_int) as libc::sighandler_t,
+                )
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Applies the limits to the current process.
+    #[cfg(not(target_os = "linux"))]
+    pub fn apply(&self) -> Result<(), String> {
+        if self.max_memory.is_some() || self.max_cpu_time.is_some() {
+            eprintln!("Warning: resource limits are only supported on Linux and will be ignored.");
+        }
+        Ok(())
+    }
+
+    /// Checks whether a solution process with these limits was terminated for exceeding one of them.
+    /// `stderr` is the error output of the process. Stack overflows are detected whether or not a stack size was set.
+    pub fn exceeded(&self, status: &ExitStatus, stderr: &[String]) -> Option<LimitExceeded> {
+        if status.success() {
+            return None;
+        }
+
+        // e.g. `thread 'Part 1' has overflowed its stack`, after which the process aborts.
+        if let Some(line) = stderr
+            .iter()
+            .find(|l| l.ends_with("has overflowed its stack"))
+        {
+            let part = line
+                .split('\'')
+                .nth(1)
+                .filter(|name| name.starts_with("Part "))
+                .map(str::to_string);
+            return Some(LimitExceeded::Stack(part));
+        }
+
+        if self.max_memory.is_some()
+            && stderr
+                .iter()
+                .any(|l| l.starts_with("memory allocation of") && l.ends_with("failed"))
+        {
+            return Some(LimitExceeded::Memory);
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            use std::os::unix::process::ExitStatusExt;
+
+            // SIGXCPU is sent at the soft limit, SIGKILL at the hard limit. Other causes of SIGKILL,
+            // e.g. the OOM killer, are crashes, so it only counts if the soft limit was reached before.
+            let reached_soft_limit = || stderr.iter().any(|l| l == CPU_TIME_EXCEEDED);
+            let is_exceeded = match status.signal() {
+                Some(libc::SIGXCPU) => true,
+                Some(libc::SIGKILL) => reached_soft_limit(),
+                _ => false,
+            };
+
+            if self.max_cpu_time.is_some() && is_exceeded {
+                return Some(LimitExceeded::CpuTime);
+            }
+        }
+
+        None
+    }
+}
+
+/// Reports the soft CPU time limit on stderr, then terminates the process with SIGXCPU as it would without a handler.
+/// Should that fail, the hard limit still kills the process, which [`Limits::exceeded`] can then tell from a crash.
+#[cfg(target_os = "linux")]
+extern "C" fn on_cpu_time_exceeded(_: libc::c_int) {
+    // a signal handler must not allocate, so the line is written in pieces.
+    for part in ["\n", CPU_TIME_EXCEEDED, "\n"] {
+        // SAFETY: `write`, `signal` and `raise` are async-signal-safe.
+        unsafe { libc::write(libc::STDERR_FILENO, part.as_ptr().cast(), part.len()) };
+    }
+
+    // SAFETY: see above. The raised signal is delivered once the handler returns.
+    unsafe {
+        libc::signal(libc::SIGXCPU, libc::SIG_DFL);
+        libc::raise(libc::SIGXCPU);
+    }
+}
+
+/// Applies the limits passed to the current solution binary, exiting on invalid arguments.
+pub fn apply_from_args() {
+    if let Err(e) = Limits::from_args().and_then(|limits| limits.apply()) {
+        eprintln!("Failed to apply resource limits: {e}");
+        std::process::exit(1);
+    }
+}
+
+/// Parses a size in bytes with an optional binary suffix, e.g. `512M` or `2G`.
+pub fn parse_bytes(s: &str) -> Result<u64, String> {
+    let s = s.trim();
+    let (digits, multiplier) = match s.char_indices().last() {
+        Some((i, 'K' | 'k')) => (&s[..i], 1 << 10),
+        Some((i, 'M' | 'm')) => (&s[..i], 1 << 20),
+        Some((i, 'G' | 'g')) => (&s[..i], 1 << 30),
+        _ => (s, 1),
+    };
+
+    digits
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(multiplier))
+        .ok_or_else(|| format!("expecting a size like `512M` or `2G`, found `{s}`"))
+}
+
+/// Parses a duration in whole seconds, e.g. `30`, `30s`, `2m` or `1h`. A number without a unit is in seconds.
+/// Other units are those of [`parse_duration`], and fractions of a second are rounded up.
+pub fn parse_seconds(s: &str) -> Result<u64, String> {
+    let s = s.trim();
+
+    if let Ok(seconds) = s.parse::<u64>() {
+        return Ok(seconds);
+    }
+
+    let duration = parse_duration(s)
+        .map_err(|_| format!("expecting a duration like `30s` or `2m`, found `{s}`"))?;
+    Ok(duration.as_secs() + u64::from(duration.subsec_nanos() > 0))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_bytes, parse_seconds, LimitExceeded, Limits, CPU_TIME_EXCEEDED};
+
+    #[test]
+    fn parses_bytes() {
+        assert_eq!(parse_bytes("1024"), Ok(1024));
+        assert_eq!(parse_bytes("4K"), Ok(4096));
+        assert_eq!(parse_bytes("512M"), Ok(512 * 1024 * 1024));
+        assert_eq!(parse_bytes("2g"), Ok(2 * 1024 * 1024 * 1024));
+        assert!(parse_bytes("2T").is_err());
+        assert!(parse_bytes("M").is_err());
+    }
+
+    #[test]
+    fn parses_seconds() {
+        assert_eq!(parse_seconds("30"), Ok(30));
+        assert_eq!(parse_seconds("30s"), Ok(30));
+        assert_eq!(parse_seconds("2m"), Ok(120));
+        assert_eq!(parse_seconds("1h"), Ok(3600));
+        assert_eq!(parse_seconds("1500ms"), Ok(2));
+        assert!(parse_seconds("-1").is_err());
+    }
+
+    #[test]
+    fn forwards_limits_as_args() {
+        let limits = Limits {
+            max_memory: Some(1024),
+            max_cpu_time: Some(10),
+            stack_size: Some(4096),
+        };
+        assert_eq!(
+            limits.to_args(),
+            vec![
+                "--max-memory",
+                "1024",
+                "--max-cpu-time",
+                "10",
+                "--stack-size",
+                "4096"
+            ]
+        );
+        assert_eq!(Limits::default().to_args(), Vec::<String>::new());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn detects_stack_overflows() {
+        use std::os::unix::process::ExitStatusExt;
+        use std::process::ExitStatus;
+
+        // aborted by SIGABRT.
+        let status = ExitStatus::from_raw(6);
+        let stderr = |line: &str| vec![String::new(), line.to_string()];
+
+        assert_eq!(
+            Limits::default()
+                .exceeded(&status, &stderr("thread 'Part 2' has overflowed its stack")),
+            Some(LimitExceeded::Stack(Some("Part 2".into())))
+        );
+        assert_eq!(
+            Limits::default().exceeded(&status, &stderr("thread 'main' has overflowed its stack")),
+            Some(LimitExceeded::Stack(None))
+        );
+        assert_eq!(
+            LimitExceeded::Stack(Some("Part 2".into())).to_string(),
+            "stack overflow in Part 2, try a larger `--stack-size`"
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn detects_cpu_time_overruns() {
+        use std::os::unix::process::ExitStatusExt;
+        use std::process::ExitStatus;
+
+        let limits = Limits {
+            max_cpu_time: Some(1),
+            ..Limits::default()
+        };
+        let (sigxcpu, sigkill) = (
+            ExitStatus::from_raw(libc::SIGXCPU),
+            ExitStatus::from_raw(libc::SIGKILL),
+        );
+
+        assert_eq!(limits.exceeded(&sigxcpu, &[]), Some(LimitExceeded::CpuTime));
+        assert_eq!(
+            limits.exceeded(&sigkill, &[CPU_TIME_EXCEEDED.to_string()]),
+            Some(LimitExceeded::CpuTime)
+        );
+
+        // e.g. the OOM killer or `kill -9`.
+        assert_eq!(limits.exceeded(&sigkill, &[]), None);
+        assert_eq!(Limits::default().exceeded(&sigxcpu, &[]), None);
+    }
+}
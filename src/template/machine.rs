@@ -0,0 +1,211 @@
+/// Captures the environment benchmarks run in, so that timings from different machines can be compared.
+/// Timed runs can also pin the solution process to a single CPU via `--pin-cpu`.
+use std::fmt::Display;
+use std::process::Command;
+use std::{env, fs};
+
+/// Where and how a set of timings was measured.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Metadata {
+    /// Output of `rustc --version`.
+    pub rustc: Option<String>,
+    /// The cargo profile the solutions were built with, or a comma-separated list when comparing profiles.
+    pub profile: String,
+    /// CPU model as reported by `/proc/cpuinfo`.
+    pub cpu_model: Option<String>,
+    /// Short hash of the checked out commit, suffixed with `-dirty` if the tree has uncommitted changes.
+    pub git_commit: Option<String>,
+    /// The CPU the solution processes were pinned to, if any.
+    pub pinned_cpu: Option<usize>,
+}
+
+impl Metadata {
+    /// Collects the metadata of the current machine and working tree.
+    pub fn collect(profile: &str, pinned_cpu: Option<usize>) -> Self {
+        Self {
+            rustc: command_output("rustc", &["--version"]),
+            profile: profile.into(),
+            cpu_model: fs::read_to_string("/proc/cpuinfo")
+                .ok()
+                .and_then(|cpuinfo| parse_cpu_model(&cpuinfo)),
+            git_commit: command_output("git", &["rev-parse", "--short", "HEAD"]).map(|commit| {
+                match command_output("git", &["status", "--porcelain"]) {
+                    Some(_) => format!("{commit}-dirty"),
+                    None => commit,
+                }
+            }),
+            pinned_cpu,
+        }
+    }
+}
+
+impl Display for Metadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}, {} profile{}, {}, commit {}",
+            self.rustc.as_deref().unwrap_or("unknown rustc"),
+            self.profile,
+            if self.profile.contains(',') { "s" } else { "" },
+            self.cpu_model.as_deref().unwrap_or("unknown CPU"),
+            self.git_commit.as_deref().unwrap_or("unknown")
+        )?;
+
+        if let Some(cpu) = self.pinned_cpu {
+            write!(f, ", pinned to CPU {cpu}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Runs a command and returns its trimmed stdout, or [`None`] if it failed or printed nothing.
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !stdout.is_empty()).then_some(stdout)
+}
+
+fn parse_cpu_model(cpuinfo: &str) -> Option<String> {
+    cpuinfo
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(key, _)| matches!(key.trim(), "model name" | "Model" | "Hardware"))
+        .map(|(_, value)| value.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|model| !model.is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Prints a warning for every CPU whose frequency governor is not `performance`.
+/// Only the pinned CPU is checked if there is one. Machines without cpufreq support are skipped silently.
+pub fn warn_about_governor(pinned_cpu: Option<usize>) {
+    let Ok(entries) = fs::read_dir("/sys/devices/system/cpu") else {
+        return;
+    };
+
+    let mut cpus = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let cpu = name.strip_prefix("cpu")?.parse::<usize>().ok()?;
+            let governor =
+                fs::read_to_string(entry.path().join("cpufreq/scaling_governor")).ok()?;
+            Some((cpu, governor.trim().to_string()))
+        })
+        .filter(|(cpu, governor)| {
+            pinned_cpu.is_none_or(|pinned| pinned == *cpu) && governor != "performance"
+        })
+        .collect::<Vec<_>>();
+
+    if cpus.is_empty() {
+        return;
+    }
+
+    cpus.sort_unstable();
+    let governors = cpus
+        .iter()
+        .map(|(cpu, governor)| format!("cpu{cpu}: {governor}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    eprintln!(
+        "Warning: the CPU frequency governor is not `performance` ({governors}). Timings may be unstable."
+    );
+}
+
+/// Pins the current process to a single CPU.
+#[cfg(target_os = "linux")]
+pub fn pin_to_cpu(cpu: usize) -> Result<(), String> {
+    // SAFETY: `cpu_set_t` is a plain bitmask, so an all-zero value is a valid empty set.
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+
+    if cpu >= libc::CPU_SETSIZE as usize {
+        return Err(format!("cpu {cpu} is out of range"));
+    }
+
+    // SAFETY: `cpu` was checked to be within the bounds of the set.
+    unsafe { libc::CPU_SET(cpu, &mut set) };
+
+    // SAFETY: `set` is a valid, initialized cpu set and its size is passed along.
+    if unsafe { libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) } == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error().to_string())
+    }
+}
+
+/// Pins the current process to a single CPU.
+#[cfg(not(target_os = "linux"))]
+pub fn pin_to_cpu(_cpu: usize) -> Result<(), String> {
+    eprintln!("Warning: pinning to a CPU is only supported on Linux and will be ignored.");
+    Ok(())
+}
+
+/// Pins the current solution binary to the CPU passed via `--pin-cpu`, exiting on invalid arguments.
+pub fn pin_from_args() {
+    let args: Vec<String> = env::args().collect();
+
+    let Some(index) = args.iter().position(|x| x == "--pin-cpu") else {
+        return;
+    };
+
+    let result = args
+        .get(index + 1)
+        .and_then(|x| x.parse::<usize>().ok())
+        .ok_or_else(|| "expecting a CPU number".to_string())
+        .and_then(pin_to_cpu);
+
+    if let Err(e) = result {
+        eprintln!("Failed to pin to CPU: {e}");
+        std::process::exit(1);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_cpu_model, Metadata};
+
+    #[test]
+    fn parses_cpu_model() {
+        let cpuinfo = "processor\t: 0\nvendor_id\t: GenuineIntel\nmodel\t\t: 143\nmodel name\t: Intel(R) Xeon(R)  Processor\n";
+        assert_eq!(
+            parse_cpu_model(cpuinfo),
+            Some("Intel(R) Xeon(R) Processor".into())
+        );
+        assert_eq!(
+            parse_cpu_model("processor\t: 0\nModel\t\t: Raspberry Pi 4 Model B Rev 1.4\n"),
+            Some("Raspberry Pi 4 Model B Rev 1.4".into())
+        );
+        assert_eq!(parse_cpu_model("processor\t: 0\n"), None);
+    }
+
+    #[test]
+    fn formats_metadata() {
+        let mut metadata = Metadata {
+            rustc: Some("rustc 1.74.0 (79e9716c9 2023-11-13)".into()),
+            profile: "release".into(),
+            cpu_model: Some("Apple M1".into()),
+            git_commit: Some("1a2b3c4".into()),
+            pinned_cpu: None,
+        };
+        assert_eq!(
+            metadata.to_string(),
+            "rustc 1.74.0 (79e9716c9 2023-11-13), release profile, Apple M1, commit 1a2b3c4"
+        );
+
+        metadata.pinned_cpu = Some(2);
+        metadata.rustc = None;
+        assert!(metadata
+            .to_string()
+            .starts_with("unknown rustc, release profile"));
+        assert!(metadata.to_string().ends_with(", pinned to CPU 2"));
+
+        metadata.profile = "release, native".into();
+        assert!(metadata.to_string().contains(", release, native profiles,"));
+    }
+}
+
+/* -------------------------------------------------------------------------- */
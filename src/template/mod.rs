@@ -1,48 +1,253 @@
 use crate::Day;
-use std::{env, fs};
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
 
+pub mod alloc;
+pub mod analyze;
 pub mod aoc_cli;
+pub mod budgets;
 pub mod commands;
+pub mod context;
+pub mod duration;
+pub mod explain;
+pub mod json;
+pub mod limits;
+pub mod machine;
+pub mod output;
+pub mod profiles;
 pub mod readme_benchmarks;
 pub mod runner;
 
-pub const ANSI_ITALIC: &str = "\x1b[3m";
-pub const ANSI_BOLD: &str = "\x1b[1m";
-pub const ANSI_RESET: &str = "\x1b[0m";
+/// Data files embedded at compile time, generated by `build.rs`.
+#[cfg(feature = "embed_inputs")]
+static EMBEDDED_DATA: &[(&str, &str)] = include!(concat!(env!("OUT_DIR"), "/embedded_data.rs"));
+
+/// Returns a data file embedded with the `embed_inputs` feature, by its path relative to the repository root.
+/// Always returns [`None`] without the feature.
+#[must_use]
+pub fn embedded_file(path: &Path) -> Option<&'static str> {
+    #[cfg(feature = "embed_inputs")]
+    {
+        let key = path.to_str()?.replace('\\', "/");
+        EMBEDDED_DATA
+            .iter()
+            .find(|(embedded, _)| *embedded == key)
+            .map(|(_, contents)| *contents)
+    }
+
+    #[cfg(not(feature = "embed_inputs"))]
+    {
+        let _ = path;
+        None
+    }
+}
+
+/// Reads a data file, e.g. `data/inputs/01.txt`. Embedded files are preferred, other relative paths
+/// are resolved against the current directory.
+pub fn read_data_file(path: &Path) -> io::Result<String> {
+    match embedded_file(path) {
+        Some(contents) => Ok(contents.to_string()),
+        None => fs::read_to_string(path),
+    }
+}
 
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
+    let filepath = Path::new("data").join(folder).join(format!("{day}.txt"));
+    let f = read_data_file(&filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
+    let filepath = Path::new("data")
         .join(folder)
         .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
+    let f = read_data_file(&filepath);
+    f.expect("could not open input file")
+}
+
+/// Helper function that returns the path of the text file for one part of a day, e.g. `data/inputs/01-2.txt`.
+/// Falls back to the file shared by both parts, e.g. `data/inputs/01.txt`, if there is no part-specific file.
+#[must_use]
+pub fn path_for_part(folder: &str, day: Day, part: u8) -> PathBuf {
+    let folder = PathBuf::from("data").join(folder);
+    let part_path = folder.join(format!("{day}-{part}.txt"));
+
+    if part_path.exists() || embedded_file(&part_path).is_some() {
+        part_path
+    } else {
+        folder.join(format!("{day}.txt"))
+    }
+}
+
+/// Helper function that returns the path of the puzzle input for one part of a day.
+/// Solution binaries read a different input if they are passed `--input <path>`.
+#[must_use]
+pub fn input_path_for_part(day: Day, part: u8) -> PathBuf {
+    let args: Vec<String> = env::args().collect();
+
+    match args
+        .iter()
+        .position(|x| x == "--input")
+        .and_then(|index| args.get(index + 1))
+    {
+        Some(path) => PathBuf::from(path),
+        None => path_for_part("inputs", day, part),
+    }
+}
+
+/// Helper function that reads the text file for one part of a day, e.g. `01-2.txt`.
+/// Falls back to the file shared by both parts, e.g. `01.txt`, if there is no part-specific file.
+#[must_use]
+pub fn read_file_for_part(folder: &str, day: Day, part: u8) -> String {
+    let f = read_data_file(&path_for_part(folder, day, part));
     f.expect("could not open input file")
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+///
+/// Solutions may offer several named variants of their parts, e.g. a naive and an optimised one.
+/// The first variant runs by default, others can be selected with `--variant <name>` or `--all-variants`:
+///
+/// ```ignore
+/// advent_of_code::solution!(6, [
+///     fast => (part_one, part_two),
+///     naive => (part_one_naive, part_two_naive),
+/// ]);
+/// ```
+///
+/// Parts may take the [`context::Context`] of their input as a second argument, e.g. to read parameters that differ
+/// between the examples and the real input.
+///
+/// A generator of inputs of a given size enables `cargo analyze`, e.g. `solution!(4, generator = generate)`.
+///
+/// Parts run on a thread with an 8 MiB stack. Deeply recursive solutions may declare a larger one,
+/// e.g. `solution!(12, stack_size = "256M")`, which `--stack-size` overrides. Options follow the variants in this order.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    ($day:expr, [$($variant:ident => ($part_one:path, $part_two:path)),+ $(,)?] $(, generator = $generator:path)? $(, stack_size = $stack_size:expr)?) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
+        #[cfg(feature = "track_allocs")]
+        #[global_allocator]
+        static ALLOCATOR: advent_of_code::template::alloc::CountingAllocator =
+            advent_of_code::template::alloc::CountingAllocator;
+
         fn main() {
+            use advent_of_code::template::context::Context;
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            use advent_of_code::template::{input_path_for_part, output, read_data_file};
+            advent_of_code::template::limits::apply_from_args();
+            advent_of_code::template::machine::pin_from_args();
+            output::init_from_args();
+
+            #[allow(unused_mut)]
+            let mut stack_size: Option<&str> = None;
+            $(stack_size = Some($stack_size);)?
+            init_stack_size(stack_size);
+
+            let variants = [$(stringify!($variant)),+];
+            let mut failed = false;
+
+            if advent_of_code::template::analyze::is_requested() {
+                use advent_of_code::template::analyze::{analyze_part, Generator};
+
+                // generated inputs have no parameters, so parts use their defaults.
+                let context = Context::default();
+
+                #[allow(unused_mut)]
+                let mut generator: Option<Generator> = None;
+                $(generator = Some($generator);)?
+
+                let Some(generator) = generator else {
+                    eprintln!("Day {DAY} has no input generator, see `cargo analyze` in the readme.");
+                    std::process::exit(1);
+                };
+
+                $(
+                    if should_run_variant(stringify!($variant), &variants) {
+                        if should_run_part(1) {
+                            analyze_part(
+                                |input: &str| Part::call(&$part_one, input, &context),
+                                generator,
+                                DAY,
+                                1,
+                                stringify!($variant),
+                            );
+                        }
+                        if should_run_part(2) {
+                            analyze_part(
+                                |input: &str| Part::call(&$part_two, input, &context),
+                                generator,
+                                DAY,
+                                2,
+                                stringify!($variant),
+                            );
+                        }
+                    }
+                )+
+
+                return;
+            }
+
+            if should_run_part(1) {
+                let path = input_path_for_part(DAY, 1);
+                output::detail(format!("Part 1 input: {}", path.display()));
+                let input = read_data_file(&path).expect("could not open input file");
+                let context = Context::for_file(&path);
+                let mut answers = vec![];
+
+                $(
+                    if should_run_variant(stringify!($variant), &variants) {
+                        let result = run_variant(
+                            |input: &str| Part::call(&$part_one, input, &context),
+                            input.as_str(),
+                            DAY,
+                            1,
+                            stringify!($variant),
+                        );
+                        failed |= result.is_failed();
+                        answers.push((stringify!($variant), result.answer()));
+                    }
+                )+
+
+                failed |= !check_variants_agree(1, &answers);
+            }
+
+            if should_run_part(2) {
+                let path = input_path_for_part(DAY, 2);
+                output::detail(format!("Part 2 input: {}", path.display()));
+                let input = read_data_file(&path).expect("could not open input file");
+                let context = Context::for_file(&path);
+                let mut answers = vec![];
+
+                $(
+                    if should_run_variant(stringify!($variant), &variants) {
+                        let result = run_variant(
+                            |input: &str| Part::call(&$part_two, input, &context),
+                            input.as_str(),
+                            DAY,
+                            2,
+                            stringify!($variant),
+                        );
+                        failed |= result.is_failed();
+                        answers.push((stringify!($variant), result.answer()));
+                    }
+                )+
+
+                failed |= !check_variants_agree(2, &answers);
+            }
+
+            if failed {
+                std::process::exit(1);
+            }
         }
     };
+    ($day:expr $(, generator = $generator:path)? $(, stack_size = $stack_size:expr)?) => {
+        $crate::solution!($day, [default => (part_one, part_two)] $(, generator = $generator)? $(, stack_size = $stack_size)?);
+    };
 }
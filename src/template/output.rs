@@ -0,0 +1,199 @@
+/// Terminal output helpers that respect `NO_COLOR`, TTY detection and the `--quiet` / `--verbose` flags.
+/// Colors and in-place progress updates (via `\r`) are only used when writing to a terminal.
+use std::env;
+use std::fmt::Display;
+use std::io::{stdout, IsTerminal};
+use std::process::Command;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+const ANSI_ITALIC: &str = "\x1b[3m";
+const ANSI_BOLD: &str = "\x1b[1m";
+const ANSI_RESET: &str = "\x1b[0m";
+
+/// Set by parent commands when their stdout is a terminal, since the stdout of solution binaries is piped.
+const TERMINAL_ENV: &str = "AOC_TERMINAL";
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+/// How much output commands produce.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Only answers and errors.
+    Quiet,
+    #[default]
+    Normal,
+    /// Additionally timings, sample counts, file paths and the commands that are run.
+    Verbose,
+}
+
+impl Verbosity {
+    /// Reads the verbosity from the `--quiet` and `--verbose` flags.
+    pub fn from_flags(quiet: bool, verbose: bool) -> Result<Self, String> {
+        match (quiet, verbose) {
+            (true, true) => Err("`--quiet` and `--verbose` cannot be combined".into()),
+            (true, false) => Ok(Verbosity::Quiet),
+            (false, true) => Ok(Verbosity::Verbose),
+            (false, false) => Ok(Verbosity::Normal),
+        }
+    }
+
+    /// Returns the arguments that pass this verbosity on to a solution binary.
+    pub fn to_args(self) -> Vec<String> {
+        match self {
+            Verbosity::Quiet => vec!["--quiet".into()],
+            Verbosity::Normal => vec![],
+            Verbosity::Verbose => vec!["--verbose".into()],
+        }
+    }
+}
+
+pub fn set_verbosity(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+pub fn verbosity() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Verbosity::Quiet,
+        2 => Verbosity::Verbose,
+        _ => Verbosity::Normal,
+    }
+}
+
+pub fn is_quiet() -> bool {
+    verbosity() == Verbosity::Quiet
+}
+
+pub fn is_verbose() -> bool {
+    verbosity() == Verbosity::Verbose
+}
+
+/// Sets the verbosity from the flags passed to the current solution binary.
+pub fn init_from_args() {
+    let args: Vec<String> = env::args().collect();
+    let has_flag = |flag: &str| args.iter().any(|x| x == flag);
+
+    match Verbosity::from_flags(has_flag("--quiet"), has_flag("--verbose")) {
+        Ok(verbosity) => set_verbosity(verbosity),
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Whether stdout is a terminal, either directly or via a parent command.
+pub fn is_terminal() -> bool {
+    static IS_TERMINAL: OnceLock<bool> = OnceLock::new();
+    *IS_TERMINAL.get_or_init(|| stdout().is_terminal() || env::var_os(TERMINAL_ENV).is_some())
+}
+
+/// Whether output may be colored. See <https://no-color.org>.
+pub fn use_color() -> bool {
+    static USE_COLOR: OnceLock<bool> = OnceLock::new();
+    *USE_COLOR.get_or_init(|| {
+        is_terminal() && env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+    })
+}
+
+/// Lets a child command know that its output ends up in a terminal, even though its stdout is piped.
+pub fn forward_terminal(cmd: &mut Command) {
+    if is_terminal() {
+        cmd.env(TERMINAL_ENV, "1");
+    }
+}
+
+pub fn bold(s: impl Display) -> String {
+    style(ANSI_BOLD, s)
+}
+
+pub fn italic(s: impl Display) -> String {
+    style(ANSI_ITALIC, s)
+}
+
+fn style(code: &str, s: impl Display) -> String {
+    if use_color() {
+        format!("{code}{s}{ANSI_RESET}")
+    } else {
+        s.to_string()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Prints a status message, unless `--quiet` is set.
+pub fn status(message: impl Display) {
+    if !is_quiet() {
+        println!("{message}");
+    }
+}
+
+/// Prints a detail message, only if `--verbose` is set.
+pub fn detail(message: impl Display) {
+    if is_verbose() {
+        println!("{message}");
+    }
+}
+
+/// Formats rows as left-aligned columns separated by two spaces.
+pub fn table(rows: &[Vec<String>]) -> String {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let widths = (0..columns)
+        .map(|col| {
+            rows.iter()
+                .filter_map(|r| r.get(col))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{table, Verbosity};
+
+    #[test]
+    fn verbosity_from_flags() {
+        assert_eq!(Verbosity::from_flags(false, false), Ok(Verbosity::Normal));
+        assert_eq!(Verbosity::from_flags(true, false), Ok(Verbosity::Quiet));
+        assert_eq!(Verbosity::from_flags(false, true), Ok(Verbosity::Verbose));
+        assert!(Verbosity::from_flags(true, true).is_err());
+    }
+
+    #[test]
+    fn forwards_verbosity_as_args() {
+        assert_eq!(Verbosity::Quiet.to_args(), vec!["--quiet"]);
+        assert_eq!(Verbosity::Normal.to_args(), Vec::<String>::new());
+        assert_eq!(Verbosity::Verbose.to_args(), vec!["--verbose"]);
+    }
+
+    #[test]
+    fn formats_table() {
+        let rows = vec![
+            vec!["Day".into(), "release".into()],
+            vec!["Day 01".into(), "1.2ms".into()],
+            vec!["Total".into()],
+        ];
+        assert_eq!(table(&rows), "Day     release\nDay 01  1.2ms\nTotal");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,121 @@
+/// Cargo build profiles that solutions can be run and benchmarked under.
+/// Besides the profiles defined in `Cargo.toml`, `native` is the `release` profile plus `-C target-cpu=native`.
+use std::env;
+use std::fmt::Display;
+use std::process::Command;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    name: String,
+}
+
+impl Profile {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+        }
+    }
+
+    /// The profile selected by the `--release` flag.
+    pub fn from_release_flag(is_release: bool) -> Self {
+        Self::new(if is_release { "release" } else { "dev" })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether solutions are built with optimizations, i.e. whether timings are meaningful.
+    pub fn is_optimized(&self) -> bool {
+        self.name != "dev"
+    }
+
+    /// Returns the arguments that select this profile for `cargo run`.
+    pub fn cargo_args(&self) -> Vec<String> {
+        match self.name.as_str() {
+            "dev" => vec![],
+            "release" => vec!["--release".into()],
+            name => vec!["--profile".into(), name.into()],
+        }
+    }
+
+    /// Extra flags that are passed to `rustc` via `RUSTFLAGS`.
+    pub fn rustflags(&self) -> Option<&'static str> {
+        match self.name.as_str() {
+            "native" => Some("-C target-cpu=native"),
+            _ => None,
+        }
+    }
+
+    /// Passes [`Profile::rustflags`] on to a `cargo` command, in addition to any `RUSTFLAGS` already set.
+    pub fn set_rustflags(&self, cmd: &mut Command) {
+        // every profile builds into its own directory below `target/`, so changing `RUSTFLAGS` here
+        // does not invalidate the artifacts of other profiles.
+        if let Some(flags) = self.rustflags() {
+            let flags = match env::var("RUSTFLAGS") {
+                Ok(existing) if !existing.is_empty() => format!("{existing} {flags}"),
+                _ => flags.to_string(),
+            };
+            cmd.env("RUSTFLAGS", flags);
+        }
+    }
+}
+
+impl Display for Profile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// Parses a comma-separated list of profiles, e.g. `release,release-lto,native`.
+pub fn parse_profiles(s: &str) -> Result<Vec<Profile>, String> {
+    let mut profiles: Vec<Profile> = vec![];
+
+    for name in s.split(',').map(str::trim) {
+        if name.is_empty() {
+            return Err(format!(
+                "expecting profiles like `release,native`, found `{s}`"
+            ));
+        }
+
+        let profile = Profile::new(name);
+        if !profiles.contains(&profile) {
+            profiles.push(profile);
+        }
+    }
+
+    Ok(profiles)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_profiles, Profile};
+
+    #[test]
+    fn parses_profiles() {
+        let profiles = parse_profiles("release, release-lto,native,release").unwrap();
+        let names = profiles.iter().map(Profile::name).collect::<Vec<_>>();
+        assert_eq!(names, vec!["release", "release-lto", "native"]);
+        assert!(parse_profiles("release,,native").is_err());
+        assert!(parse_profiles("").is_err());
+    }
+
+    #[test]
+    fn cargo_args() {
+        assert_eq!(Profile::new("dev").cargo_args(), Vec::<String>::new());
+        assert_eq!(Profile::new("release").cargo_args(), vec!["--release"]);
+        assert_eq!(
+            Profile::new("release-lto").cargo_args(),
+            vec!["--profile", "release-lto"]
+        );
+        assert_eq!(
+            Profile::new("native").rustflags(),
+            Some("-C target-cpu=native")
+        );
+        assert!(!Profile::from_release_flag(false).is_optimized());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
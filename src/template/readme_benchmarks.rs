@@ -2,6 +2,7 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
+use crate::template::machine::Metadata;
 use crate::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
@@ -23,6 +24,8 @@ pub struct Timings {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub part_1_allocs: Option<String>,
+    pub part_2_allocs: Option<String>,
     pub total_nanos: f64,
 }
 
@@ -58,46 +61,79 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
+fn construct_table(
+    prefix: &str,
+    timings: Vec<Timings>,
+    total_millis: f64,
+    metadata: &Metadata,
+) -> String {
     let header = format!("{prefix} Benchmarks");
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let has_allocs = timings
+        .iter()
+        .any(|t| t.part_1_allocs.is_some() || t.part_2_allocs.is_some());
+
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+
+    if has_allocs {
+        lines.push("| Day | Part 1 | Allocations | Part 2 | Allocations |".into());
+        lines.push("| :---: | :---: | :---: | :---: | :---:  |".into());
+    } else {
+        lines.push("| Day | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---:  |".into());
+    }
 
     for timing in timings {
         let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        let part_1 = timing.part_1.unwrap_or_else(|| "-".into());
+        let part_2 = timing.part_2.unwrap_or_else(|| "-".into());
+
+        if has_allocs {
+            lines.push(format!(
+                "| [Day {}]({}) | `{}` | {} | `{}` | {} |",
+                timing.day.into_inner(),
+                path,
+                part_1,
+                timing.part_1_allocs.unwrap_or_else(|| "-".into()),
+                part_2,
+                timing.part_2_allocs.unwrap_or_else(|| "-".into())
+            ));
+        } else {
+            lines.push(format!(
+                "| [Day {}]({}) | `{}` | `{}` |",
+                timing.day.into_inner(),
+                path,
+                part_1,
+                part_2
+            ));
+        }
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
+    lines.push(String::new());
+    lines.push(format!("_Measured with {metadata}._"));
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+fn update_content(
+    s: &mut String,
+    timings: Vec<Timings>,
+    total_millis: f64,
+    metadata: &Metadata,
+) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+    let table = construct_table("##", timings, total_millis, metadata);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+pub fn update(timings: Vec<Timings>, total_millis: f64, metadata: &Metadata) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, timings, total_millis, metadata)?;
     fs::write(path, &readme)?;
     Ok(())
 }
@@ -106,6 +142,17 @@ pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
 mod tests {
     use super::{update_content, Timings, MARKER};
     use crate::day;
+    use crate::template::machine::Metadata;
+
+    fn get_mock_metadata() -> Metadata {
+        Metadata {
+            rustc: Some("rustc 1.74.0".into()),
+            profile: "release".into(),
+            cpu_model: Some("Apple M1".into()),
+            git_commit: Some("1a2b3c4".into()),
+            pinned_cpu: None,
+        }
+    }
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
@@ -113,18 +160,24 @@ mod tests {
                 day: day!(1),
                 part_1: Some("10ms".into()),
                 part_2: Some("20ms".into()),
+                part_1_allocs: None,
+                part_2_allocs: None,
                 total_nanos: 3e+10,
             },
             Timings {
                 day: day!(2),
                 part_1: Some("30ms".into()),
                 part_2: Some("40ms".into()),
+                part_1_allocs: None,
+                part_2_allocs: None,
                 total_nanos: 7e+10,
             },
             Timings {
                 day: day!(4),
                 part_1: Some("40ms".into()),
                 part_2: Some("50ms".into()),
+                part_1_allocs: None,
+                part_2_allocs: None,
                 total_nanos: 9e+10,
             },
         ]
@@ -134,28 +187,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &get_mock_metadata()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &get_mock_metadata()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &get_mock_metadata()).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &get_mock_metadata()).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &get_mock_metadata()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -163,7 +216,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &get_mock_metadata()).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -177,10 +230,27 @@ mod tests {
             "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
+            "",
+            "_Measured with rustc 1.74.0, release profile, Apple M1, commit 1a2b3c4._",
             "<!--- benchmarking table --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_allocations() {
+        let mut timings = get_mock_timings();
+        timings[0].part_1_allocs = Some("3 allocs, 1.0 KiB total, 512 B peak".into());
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &get_mock_metadata()).unwrap();
+
+        assert!(s.contains("| Day | Part 1 | Allocations | Part 2 | Allocations |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `10ms` | 3 allocs, 1.0 KiB total, 512 B peak | `20ms` | - |"
+        ));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `30ms` | - | `40ms` | - |"));
+    }
 }
@@ -1,63 +1,320 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::template::alloc::{self, AllocStats};
+use crate::template::context::Context;
+use crate::template::limits::{parse_bytes, Limits};
+use crate::template::{aoc_cli, explain, input_path_for_part, output};
+use crate::{ocr, Day};
 use std::fmt::Display;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::process::{Command, Output};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, panic, process, thread};
 
-use super::ANSI_BOLD;
+/// Printed when the variants of a solution part return different answers.
+pub const VARIANTS_DISAGREE: &str = "variants disagree";
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// Parts run on threads with the stack size of a typical main thread, so that solutions behave as if they ran on it.
+const DEFAULT_STACK_SIZE: usize = 8 << 20;
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+static STACK_SIZE: AtomicUsize = AtomicUsize::new(DEFAULT_STACK_SIZE);
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+/// The outcome of running a solution part.
+pub enum Outcome<T> {
+    /// The part returned an answer.
+    Solved(T),
+    /// The part returned [`None`], i.e. it has not been implemented yet.
+    Unsolved,
+    /// The part returned an error, e.g. because it hit malformed input.
+    Failed(String),
+}
+
+impl<T> Outcome<T> {
+    pub fn is_failed(&self) -> bool {
+        matches!(self, Outcome::Failed(_))
+    }
+}
+
+impl<T: Display> Outcome<T> {
+    /// Returns the formatted answer if the part was solved.
+    pub fn answer(&self) -> Option<String> {
+        match self {
+            Outcome::Solved(answer) => Some(answer.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Return types that solution parts may use.
+pub trait PartResult {
+    type Answer: Display;
+
+    fn into_outcome(self) -> Outcome<Self::Answer>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    type Answer = T;
+
+    fn into_outcome(self) -> Outcome<T> {
+        match self {
+            Some(answer) => Outcome::Solved(answer),
+            None => Outcome::Unsolved,
+        }
+    }
+}
+
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    type Answer = T;
+
+    fn into_outcome(self) -> Outcome<T> {
+        match self {
+            Ok(answer) => Outcome::Solved(answer),
+            Err(e) => Outcome::Failed(e.to_string()),
+        }
+    }
+}
+
+/// Functions that `solution!` accepts as parts: either `fn(&str) -> R`, or `fn(&str, &Context) -> R` for parts
+/// that need to know whether they run on an example, or need parameters of their input.
+/// `M` is one of [`WithoutContext`] and [`WithContext`] and only tells the two signatures apart.
+pub trait Part<M, R> {
+    fn call(&self, input: &str, context: &Context) -> R;
+}
+
+pub struct WithoutContext;
+
+pub struct WithContext;
+
+impl<F: Fn(&str) -> R, R> Part<WithoutContext, R> for F {
+    fn call(&self, input: &str, _: &Context) -> R {
+        self(input)
+    }
+}
+
+impl<F: Fn(&str, &Context) -> R, R> Part<WithContext, R> for F {
+    fn call(&self, input: &str, context: &Context) -> R {
+        self(input, context)
+    }
+}
+
+/// Sets the stack size of the threads that run the parts to the size passed via `--stack-size`,
+/// or else to the size declared via `solution!(.., stack_size = "256M")`. Exits on invalid sizes.
+pub fn init_stack_size(declared: Option<&str>) {
+    let size = Limits::from_args().and_then(|limits| match (limits.stack_size, declared) {
+        (Some(bytes), _) => Ok(Some(bytes)),
+        (None, Some(declared)) => parse_bytes(declared).map(Some),
+        (None, None) => Ok(None),
+    });
+
+    match size {
+        Ok(Some(bytes)) => STACK_SIZE.store(
+            usize::try_from(bytes).unwrap_or(usize::MAX),
+            Ordering::Relaxed,
+        ),
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("Invalid stack size: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Runs `func` on a thread called `name` with the configured stack size.
+/// A stack overflow aborts the process with a message naming the thread, which `solve` and `all` report as a failure of the part.
+/// Panics are resumed on the calling thread.
+pub fn on_worker_thread<T: Send>(name: &str, func: impl FnOnce() -> T + Send) -> T {
+    thread::scope(|scope| {
+        thread::Builder::new()
+            .name(name.to_string())
+            .stack_size(STACK_SIZE.load(Ordering::Relaxed))
+            .spawn_scoped(scope, func)
+            .expect("failed to spawn thread")
+            .join()
+            .unwrap_or_else(|e| panic::resume_unwind(e))
+    })
+}
+
+pub fn run_part<I: Clone + Send, R: PartResult>(
+    func: impl Fn(I) -> R + Sync,
+    input: I,
+    day: Day,
+    part: u8,
+) -> Outcome<R::Answer>
+where
+    R::Answer: Send,
+{
+    run_labelled(func, input, day, part, &format!("Part {part}"))
+}
+
+/// Run one variant of a solution part.
+/// The variant is named in the output if variants were selected explicitly via `--variant` or `--all-variants`.
+pub fn run_variant<I: Clone + Send, R: PartResult>(
+    func: impl Fn(I) -> R + Sync,
+    input: I,
+    day: Day,
+    part: u8,
+    variant: &str,
+) -> Outcome<R::Answer>
+where
+    R::Answer: Send,
+{
+    let is_selected = env::args().any(|x| x == "--variant" || x == "--all-variants");
+
+    let part_str = if is_selected {
+        format!("Part {part} ({variant})")
+    } else {
+        format!("Part {part}")
+    };
+
+    run_labelled(func, input, day, part, &part_str)
+}
+
+fn run_labelled<I: Clone + Send, R: PartResult>(
+    func: impl Fn(I) -> R + Sync,
+    input: I,
+    day: Day,
+    part: u8,
+    part_str: &str,
+) -> Outcome<R::Answer>
+where
+    R::Answer: Send,
+{
+    let rerun_input = input.clone();
+    let (result, duration, samples, allocs) = on_worker_thread(part_str, || {
+        run_timed(
+            |input| func(input).into_outcome(),
+            input,
+            |result| print_result(result, part_str, ""),
+        )
+    });
+
+    let stats_str = format_duration(&duration, samples) + &format_allocs(allocs.as_ref());
+    print_result(&result, part_str, &stats_str);
+
+    let rows = explain::take();
+    if !rows.is_empty() {
+        println!("{}", explain::format_table(&rows));
+    }
+
+    if let Outcome::Solved(result) = &result {
+        let rerun = || {
+            let input = rerun_input.clone();
+            on_worker_thread(part_str, || match func(input).into_outcome() {
+                Outcome::Solved(answer) => answer.to_string(),
+                Outcome::Unsolved => "✖".to_string(),
+                Outcome::Failed(e) => format!("⚠ failed, {e}"),
+            })
+        };
+        submit_result(result, day, part, rerun);
+    }
+
+    result
+}
+
+/// Parse the `--part` argument passed to `solve` and check whether the given part should run.
+/// All parts run if no part is selected.
+pub fn should_run_part(part: u8) -> bool {
+    let args: Vec<String> = env::args().collect();
+
+    let Some(part_index) = args.iter().position(|x| x == "--part") else {
+        return true;
+    };
+
+    let Some(Ok(part_selected)) = args.get(part_index + 1).map(|x| x.parse::<u8>()) else {
+        eprintln!("Unexpected command-line input. Format: cargo solve 1 --part 1");
+        process::exit(1);
+    };
+
+    part_selected == part
+}
+
+/// Parse the `--variant` and `--all-variants` arguments passed to `solve` and check whether the given variant should run.
+/// Only the first of `variants` runs if no variant is selected.
+pub fn should_run_variant(variant: &str, variants: &[&str]) -> bool {
+    let args: Vec<String> = env::args().collect();
+
+    if args.iter().any(|x| x == "--all-variants") {
+        return true;
+    }
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    let Some(variant_index) = args.iter().position(|x| x == "--variant") else {
+        return variants.first() == Some(&variant);
+    };
+
+    let Some(variant_selected) = args.get(variant_index + 1) else {
+        eprintln!("Unexpected command-line input. Format: cargo solve 1 --variant fast");
+        process::exit(1);
+    };
+
+    if !variants.contains(&variant_selected.as_str()) {
+        eprintln!(
+            "Unknown variant `{variant_selected}`. Available variants: {}",
+            variants.join(", ")
+        );
+        process::exit(1);
+    }
+
+    variant_selected == variant
+}
+
+/// Check that all variants that solved a part agree on the answer, printing a failure with all answers if not.
+pub fn check_variants_agree(part: u8, answers: &[(&str, Option<String>)]) -> bool {
+    let solved = answers
+        .iter()
+        .filter_map(|(variant, answer)| Some((variant, answer.as_ref()?)))
+        .collect::<Vec<_>>();
+
+    if solved.windows(2).all(|w| w[0].1 == w[1].1) {
+        return true;
     }
+
+    println!("Part {part}: ⚠ failed, {VARIANTS_DISAGREE}");
+    for (variant, answer) in solved {
+        println!("  {variant}: {answer}");
+    }
+
+    false
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// Parts that fail are never benched.
+/// Allocations are only counted for the first execution, and only if the `track_allocs` feature is enabled.
+/// The same goes for values recorded via `explain!` and the `explain` feature.
 fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
+    func: impl Fn(I) -> Outcome<T>,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    hook: impl Fn(&Outcome<T>),
+) -> (Outcome<T>, Duration, u128, Option<AllocStats>) {
+    alloc::reset();
+    explain::start();
     let timer = Instant::now();
     let result = func(input.clone());
     let base_time = timer.elapsed();
+    let allocs = alloc::stats();
+    explain::stop();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let run = if !result.is_failed() && std::env::args().any(|x| x == "--time") {
         bench(func, input, &base_time)
     } else {
         (base_time, 1)
     };
 
-    (result, run.0, run.1)
+    (result, run.0, run.1, allocs)
 }
 
 fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    if output::is_terminal() && !output::is_quiet() {
+        print!(" > {}", output::italic("benching"));
+        let _ = stdout().flush();
+    }
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -85,55 +342,80 @@ fn average_duration(numbers: &[Duration]) -> u128 {
 }
 
 fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
+    if samples == 1 && !output::is_verbose() {
         format!(" ({duration:.1?})")
     } else {
         format!(" ({duration:.1?} @ {samples} samples)")
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn format_allocs(allocs: Option<&AllocStats>) -> String {
+    allocs.map_or_else(String::new, |allocs| format!(" [{allocs}]"))
+}
+
+/// Prints the result of a part. Results without `duration_str` are intermediate: they are shown while
+/// the part is benched and overwritten by the final result, which is only possible in a terminal.
+fn print_result<T: Display>(result: &Outcome<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
+    if is_intermediate_result && (!output::is_terminal() || output::is_quiet()) {
+        return;
+    }
+
+    // `--quiet` only prints answers and errors.
+    let duration_str = if output::is_quiet() { "" } else { duration_str };
+
+    let str = match result {
+        Outcome::Solved(result) => {
+            let answer = result.to_string();
+            if answer.contains('\n') {
+                // letters drawn in the Advent of Code font are shown decoded, followed by the drawing.
+                match ocr::decode(&answer) {
+                    Some(text) => format!("{part}: {} ▼{duration_str}", output::bold(text)),
+                    None => format!("{part}: ▼ {duration_str}"),
                 }
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
+                format!("{part}: {}{duration_str}", output::bold(result))
             }
         }
-        None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
-            } else {
-                print!("\r");
-                println!("{part}: ✖             ");
-            }
+        Outcome::Unsolved if output::is_quiet() => return,
+        // pad to overwrite the intermediate result.
+        Outcome::Unsolved if output::is_terminal() && !is_intermediate_result => {
+            format!("{part}: ✖             ")
         }
+        Outcome::Unsolved => format!("{part}: ✖"),
+        Outcome::Failed(_) => format!("{part}: ⚠ failed{duration_str}"),
+    };
+
+    if is_intermediate_result {
+        print!("{str}");
+        let _ = stdout().flush();
+        return;
+    }
+
+    if output::is_terminal() {
+        print!("\r");
+    }
+
+    println!("{str}");
+
+    match result {
+        Outcome::Solved(result) if result.to_string().contains('\n') => println!("{result}"),
+        Outcome::Failed(err) => println!("{err}"),
+        _ => {}
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
+///
+/// The answer is checked with [`check_answer`] first. Answers drawn as letters are submitted as the decoded text.
 fn submit_result<T: Display>(
-    result: T,
+    result: &T,
     day: Day,
     part: u8,
+    rerun: impl Fn() -> String,
 ) -> Option<Result<Output, aoc_cli::AocCommandError>> {
     let args: Vec<String> = env::args().collect();
 
@@ -162,6 +444,132 @@ fn submit_result<T: Display>(
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let mut answer = result.to_string();
+
+    if let Err(discrepancy) = check_answer(&answer, day, part, rerun) {
+        eprintln!("Not submitting part {part}: {discrepancy}");
+        process::exit(1);
+    }
+
+    if answer.contains('\n') {
+        let Some(text) = ocr::decode(&answer) else {
+            eprintln!("The answer of part {part} spans several lines and could not be read as letters, submit it manually.");
+            process::exit(1);
+        };
+        answer = text;
+    }
+
+    output::status(format!("Submitting {answer} via aoc-cli..."));
+    Some(aoc_cli::submit(day, part, &answer))
+}
+
+/// Guards against wrong submissions, which lock the puzzle for a while, by checking that the part returns the same answer:
+///  1. twice more, to catch nondeterminism, e.g. iterating a `HashMap` whose order differs between instances.
+///  2. in a debug build, which panics on integer overflows instead of wrapping. Skipped if this is a debug build already.
+///
+/// Returns a description of the discrepancy if any answer differs.
+fn check_answer(
+    answer: &str,
+    day: Day,
+    part: u8,
+    rerun: impl Fn() -> String,
+) -> Result<(), String> {
+    output::status(format!(
+        "Checking the answer of part {part} before submitting..."
+    ));
+
+    for run in 2..=3 {
+        let rerun_answer = rerun();
+        if rerun_answer.trim_end() != answer.trim_end() {
+            return Err(format!(
+                "run {run} returned a different answer, the solution may be nondeterministic.\n  run 1: {answer}\n  run {run}: {rerun_answer}"
+            ));
+        }
+    }
+
+    if cfg!(debug_assertions) {
+        return Ok(());
+    }
+
+    output::status("Running the part in a debug build to check for integer overflows...");
+    let debug_answer = run_debug_build(day, part)?;
+
+    if debug_answer.trim_end() == answer.trim_end() {
+        Ok(())
+    } else {
+        Err(format!(
+            "the debug build returned a different answer.\n  release: {answer}\n  debug: {debug_answer}"
+        ))
+    }
+}
+
+/// Runs a part in a debug build on the same input and returns its answer.
+fn run_debug_build(day: Day, part: u8) -> Result<String, String> {
+    let args: Vec<String> = env::args().collect();
+    let limits = Limits::from_args().unwrap_or_default();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--quiet", "--bin", &day.to_string(), "--"])
+        .args(["--quiet", "--part", &part.to_string(), "--input"])
+        .arg(input_path_for_part(day, part))
+        .args(limits.to_args());
+
+    if let Some(variant) = args
+        .iter()
+        .position(|x| x == "--variant")
+        .and_then(|index| args.get(index + 1))
+    {
+        cmd.args(["--variant", variant]);
+    }
+
+    let result = cmd
+        .output()
+        .map_err(|e| format!("failed to run the debug build: {e}"))?;
+
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    let stderr = String::from_utf8_lossy(&result.stderr);
+
+    if !result.status.success() {
+        // e.g. `attempt to multiply with overflow`.
+        return Err(format!(
+            "the debug build failed.\n{}{}",
+            stdout,
+            stderr.trim_end()
+        ));
+    }
+
+    parse_answer(&stdout, part).ok_or_else(|| "the debug build returned no answer.".to_string())
 }
+
+/// Parses the answer of a part from the output of a solution run with `--quiet`, including multi-line answers.
+fn parse_answer(stdout: &str, part: u8) -> Option<String> {
+    let mut lines = stdout.lines();
+    let (_, answer) = lines
+        .find(|line| line.starts_with(&format!("Part {part}")))?
+        .split_once(": ")?;
+
+    if answer.trim_end().ends_with('▼') {
+        let art = lines.take_while(|line| !line.starts_with("Part "));
+        Some(art.collect::<Vec<_>>().join("\n"))
+    } else {
+        Some(answer.trim_end().to_string())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::parse_answer;
+
+    #[test]
+    fn parses_answers() {
+        let stdout = "Part 1: 42\nPart 2: EZ ▼\n####\n#...\n";
+        assert_eq!(parse_answer(stdout, 1), Some("42".into()));
+        assert_eq!(parse_answer(stdout, 2), Some("####\n#...".into()));
+        assert_eq!(parse_answer("Part 1 (fast): 7\n", 1), Some("7".into()));
+        assert_eq!(parse_answer("", 1), None);
+    }
+}
+
+/* -------------------------------------------------------------------------- */